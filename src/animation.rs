@@ -0,0 +1,189 @@
+use crate::prelude::*;
+
+const MOVE_STEP_SECONDS: f32 = 0.15;
+const HIT_EFFECT_SECONDS: f32 = 0.6;
+
+pub struct MoveAnimation {
+    pub entity: Entity,
+    pub path: Vec<Coordinate>,
+    pub elapsed: f32,
+}
+
+impl MoveAnimation {
+    pub fn duration(&self) -> f32 {
+        self.path.len().saturating_sub(1) as f32 * MOVE_STEP_SECONDS
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration()
+    }
+
+    // Where the piece should be drawn, easing between cell centers one step at a time.
+    pub fn get_screen_pos(&self, grid: &BattleGrid) -> Vec2 {
+        let last = match self.path.last() {
+            Some(last) => *last,
+            None => return Vec2::ZERO,
+        };
+
+        let progress = (self.elapsed / MOVE_STEP_SECONDS).max(0.0);
+        let step = progress.floor() as usize;
+
+        match (self.path.get(step), self.path.get(step + 1)) {
+            (Some(from), Some(to)) => {
+                let t = ease_in_out(progress.fract());
+                let from_pos = grid.get_cell_center(from.x, from.y);
+                let to_pos = grid.get_cell_center(to.x, to.y);
+
+                from_pos.lerp(to_pos, t)
+            }
+
+            _ => grid.get_cell_center(last.x, last.y),
+        }
+    }
+}
+
+pub struct HitEffect {
+    pub coord: Coordinate,
+    pub amount: i32,
+    pub elapsed: f32,
+}
+
+impl HitEffect {
+    // 0.0 when the hit lands, 1.0 once the effect has faded.
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / HIT_EFFECT_SECONDS).clamp(0.0, 1.0)
+    }
+}
+
+pub struct Animations {
+    pub moves: Vec<MoveAnimation>,
+    pub hits: Vec<HitEffect>,
+}
+
+impl Animations {
+    pub fn new() -> Self {
+        Self {
+            moves: Vec::new(),
+            hits: Vec::new(),
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &CombatEvent) {
+        match event {
+            CombatEvent::Moved { entity, path } => self.moves.push(MoveAnimation {
+                entity: *entity,
+                path: path.clone(),
+                elapsed: 0.0,
+            }),
+
+            CombatEvent::Damaged { coord, amount, .. } => self.hits.push(HitEffect {
+                coord: *coord,
+                amount: *amount,
+                elapsed: 0.0,
+            }),
+
+            CombatEvent::CardPlayed { .. }
+            | CombatEvent::CardResolved { .. }
+            | CombatEvent::Healed { .. }
+            | CombatEvent::Died { .. } => {}
+        }
+    }
+
+    // Pieces finish walking before any hits land, so attacks read as following the movement.
+    pub fn update(&mut self, dt: f32) {
+        self.moves.iter_mut().for_each(|anim| anim.elapsed += dt);
+        self.moves.retain(|anim| !anim.is_finished());
+
+        if self.moves.is_empty() {
+            self.hits.iter_mut().for_each(|hit| hit.elapsed += dt);
+            self.hits.retain(|hit| hit.progress() < 1.0);
+        }
+    }
+
+    pub fn skip(&mut self) {
+        self.moves.clear();
+        self.hits.clear();
+    }
+
+    pub fn is_busy(&self) -> bool {
+        !self.moves.is_empty() || !self.hits.is_empty()
+    }
+
+    pub fn get_piece_screen_pos(&self, entity: &Entity, grid: &BattleGrid) -> Option<Vec2> {
+        self.moves
+            .iter()
+            .find(|anim| anim.entity == *entity)
+            .map(|anim| anim.get_screen_pos(grid))
+    }
+}
+
+fn ease_in_out(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(entity: Entity) -> CombatEvent {
+        CombatEvent::Moved {
+            entity,
+            path: vec![
+                Coordinate { x: 0, y: 0 },
+                Coordinate { x: 1, y: 0 },
+                Coordinate { x: 1, y: 1 },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_move_animation_walks_through_each_cell() {
+        let mut world = World::default();
+        let entity = world.push(());
+        let grid = BattleGrid::new();
+        let mut animations = Animations::new();
+        animations.handle_event(&walk(entity));
+
+        assert_eq!(
+            animations.get_piece_screen_pos(&entity, &grid),
+            Some(grid.get_cell_center(0, 0))
+        );
+
+        animations.update(MOVE_STEP_SECONDS);
+        assert_eq!(
+            animations.get_piece_screen_pos(&entity, &grid),
+            Some(grid.get_cell_center(1, 0))
+        );
+
+        animations.update(MOVE_STEP_SECONDS * 0.5);
+        let halfway = animations.get_piece_screen_pos(&entity, &grid).unwrap();
+        let expected = grid
+            .get_cell_center(1, 0)
+            .lerp(grid.get_cell_center(1, 1), 0.5);
+        assert!(halfway.distance(expected) < 0.01);
+
+        animations.update(MOVE_STEP_SECONDS);
+        assert!(!animations.is_busy());
+        assert_eq!(animations.get_piece_screen_pos(&entity, &grid), None);
+    }
+
+    #[test]
+    fn test_hits_wait_for_moves_to_finish() {
+        let mut world = World::default();
+        let entity = world.push(());
+        let mut animations = Animations::new();
+        animations.handle_event(&walk(entity));
+        animations.handle_event(&CombatEvent::Damaged {
+            entity,
+            coord: Coordinate { x: 2, y: 1 },
+            amount: 2,
+        });
+
+        animations.update(MOVE_STEP_SECONDS);
+        assert_eq!(animations.hits[0].progress(), 0.0);
+
+        animations.update(MOVE_STEP_SECONDS);
+        animations.update(HIT_EFFECT_SECONDS);
+        assert!(!animations.is_busy());
+    }
+}
@@ -0,0 +1,95 @@
+use crate::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+// Textures loaded from the assets folder the first time they're asked for. Anything that can't be
+// loaded is remembered as missing, so callers fall back to drawing plain shapes without going
+// back to the disk every frame.
+pub struct TextureAssets {
+    root: String,
+    loaded: HashMap<String, Texture2D>,
+    missing: HashSet<String>,
+}
+
+impl TextureAssets {
+    pub fn new(root: &str) -> Self {
+        Self {
+            root: root.to_string(),
+            loaded: HashMap::new(),
+            missing: HashSet::new(),
+        }
+    }
+
+    // `path` is relative to the assets folder, e.g. `heroes/fighter.png`.
+    pub fn get(&mut self, path: &str) -> Option<Texture2D> {
+        if let Some(texture) = self.loaded.get(path) {
+            return Some(*texture);
+        }
+
+        if self.missing.contains(path) {
+            return None;
+        }
+
+        match std::fs::read(format!("{}/{}", self.root, path)) {
+            Ok(bytes) if bytes.starts_with(&PNG_SIGNATURE) => {
+                let texture = Texture2D::from_file_with_format(&bytes, None);
+                texture.set_filter(FilterMode::Nearest);
+                self.loaded.insert(path.to_string(), texture);
+
+                Some(texture)
+            }
+
+            Ok(_) => {
+                eprintln!("{path} is not a PNG, falling back to shapes.");
+                self.missing.insert(path.to_string());
+                None
+            }
+
+            Err(_) => {
+                eprintln!("No texture found at {path}, falling back to shapes.");
+                self.missing.insert(path.to_string());
+                None
+            }
+        }
+    }
+
+    pub fn get_sprite(&mut self, sprite: Option<&Sprite>) -> Option<Texture2D> {
+        sprite.and_then(|sprite| self.get(&sprite.path))
+    }
+}
+
+pub fn draw_texture_in_rect(texture: Texture2D, rect: Rect) {
+    draw_texture_ex(
+        texture,
+        rect.x,
+        rect.y,
+        WHITE,
+        DrawTextureParams {
+            dest_size: Some(rect.size()),
+            ..Default::default()
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_textures_are_remembered() {
+        let mut textures = TextureAssets::new("./assets");
+
+        assert!(textures.get("nothing/here.png").is_none());
+        assert!(textures.missing.contains("nothing/here.png"));
+        assert!(textures.get("nothing/here.png").is_none());
+    }
+
+    #[test]
+    fn test_non_png_files_are_rejected() {
+        let mut textures = TextureAssets::new("./assets");
+
+        assert!(textures.get("bindings.cfg").is_none());
+        assert!(textures.loaded.is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+use crate::prelude::*;
+
+// Every attacker declaring damage on a single cell, used to draw the telegraph overlay.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttackPreview {
+    pub coord: Coordinate,
+    pub total_damage: i32,
+    pub sources: Vec<Entity>,
+}
+
+// Heroes aim at the cells around them, or straight at the dragon with shapes that travel. Aims
+// that hurt the dragon are always taken, and harmful ones that would catch an ally are skipped.
+pub fn choose_attack_target(
+    effects: &[Effect],
+    pos: Coordinate,
+    grid: &BattleGrid,
+    rng: &mut StdRng,
+) -> Option<Coordinate> {
+    let dragon = get_dragon_origin(grid);
+    let mut aims = pos
+        .neighbours()
+        .filter(|coord| grid.is_cell_in_bounds(*coord) || *coord == dragon)
+        .collect::<Vec<Coordinate>>();
+
+    let travels = effects.iter().any(|effect| match effect {
+        Effect::DefineTarget(shape) => shape.travels_from_caster(),
+        _ => false,
+    });
+    if travels && !aims.contains(&dragon) {
+        aims.push(dragon);
+    }
+
+    let outcomes = aims
+        .into_iter()
+        .map(|aim| (aim, interpret_effects(effects, pos, aim, grid)))
+        .collect::<Vec<(Coordinate, EffectOutcome)>>();
+
+    if let Some((aim, _)) = outcomes
+        .iter()
+        .filter(|(_, outcome)| outcome.damage > 0 && outcome.targets.contains(&dragon))
+        .choose(rng)
+    {
+        return Some(*aim);
+    }
+
+    outcomes
+        .iter()
+        .filter(|(_, outcome)| {
+            outcome.damage <= 0
+                || !outcome.targets.iter().any(|coord| {
+                    *coord != pos
+                        && grid.is_cell_in_bounds(*coord)
+                        && grid.get_status_at_coord(coord).is_occupied()
+                })
+        })
+        .map(|(aim, _)| *aim)
+        .filter(|aim| grid.is_cell_in_bounds(*aim))
+        .choose(rng)
+}
+
+// Groups declared attacks by the cell they hit, preserving declaration order so overlapping
+// telegraphs are always drawn the same way.
+pub fn build_attack_previews<'a>(
+    attacks: impl Iterator<Item = (Entity, &'a Attack)>,
+) -> Vec<AttackPreview> {
+    let mut previews: Vec<AttackPreview> = Vec::new();
+
+    attacks.for_each(|(source, attack)| {
+        attack.targets.iter().for_each(|coord| {
+            if let Some(existing) = previews.iter_mut().find(|preview| preview.coord == *coord) {
+                existing.total_damage += attack.damage;
+                if !existing.sources.contains(&source) {
+                    existing.sources.push(source);
+                }
+            } else {
+                previews.push(AttackPreview {
+                    coord: *coord,
+                    total_damage: attack.damage,
+                    sources: vec![source],
+                });
+            }
+        });
+    });
+
+    previews
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_attacks_sum_damage() {
+        let mut world = World::default();
+        let fighter = world.push((Enemy,));
+        let wizard = world.push((Enemy,));
+
+        let fighter_attack = Attack {
+            targets: vec![Coordinate { x: 1, y: 1 }, Coordinate { x: 1, y: 2 }],
+            damage: 2,
+        };
+        let wizard_attack = Attack {
+            targets: vec![Coordinate { x: 1, y: 2 }],
+            damage: 3,
+        };
+
+        let previews = build_attack_previews(
+            [(fighter, &fighter_attack), (wizard, &wizard_attack)].into_iter(),
+        );
+
+        assert_eq!(previews.len(), 2);
+        assert_eq!(previews[0].total_damage, 2);
+        assert_eq!(previews[0].sources, vec![fighter]);
+        assert_eq!(previews[1].coord, Coordinate { x: 1, y: 2 });
+        assert_eq!(previews[1].total_damage, 5);
+        assert_eq!(previews[1].sources, vec![fighter, wizard]);
+    }
+
+    fn effects(text: &str) -> Vec<Effect> {
+        get_effects_from_text(text.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_attack_target_is_adjacent_and_in_bounds() {
+        let grid = BattleGrid::new();
+        let corner = Coordinate { x: 0, y: 0 };
+        let mut rng = StdRng::seed_from_u64(7);
+
+        (0..20).for_each(|_| {
+            let target =
+                choose_attack_target(&effects("deal(1);"), corner, &grid, &mut rng).unwrap();
+            assert!(grid.is_cell_in_bounds(target));
+            assert_eq!(target.manhattan_distance(corner), 1);
+        });
+    }
+
+    #[test]
+    fn test_heroes_go_for_the_dragon() {
+        let grid = BattleGrid::new();
+        let dragon = get_dragon_origin(&grid);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let next_to_dragon = Coordinate { x: 2, y: 4 };
+        assert_eq!(
+            choose_attack_target(&effects("deal(1);"), next_to_dragon, &grid, &mut rng),
+            Some(dragon)
+        );
+
+        let archer = Coordinate { x: 2, y: 0 };
+        assert_eq!(
+            choose_attack_target(&effects("line(6); deal(2);"), archer, &grid, &mut rng),
+            Some(dragon)
+        );
+    }
+
+    #[test]
+    fn test_heroes_dont_hit_allies() {
+        let mut grid = BattleGrid::new();
+        let pos = Coordinate { x: 0, y: 0 };
+        let ally = Coordinate { x: 1, y: 0 };
+        grid.set_status_at_coord(&ally, TileStatus::Occupied);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        (0..20).for_each(|_| {
+            assert_eq!(
+                choose_attack_target(&effects("deal(1);"), pos, &grid, &mut rng),
+                Some(Coordinate { x: 0, y: 1 })
+            );
+        });
+
+        // Healing is fine to land on a friend.
+        let heal = effects("healing(1);");
+        assert!((0..20).any(|_| choose_attack_target(&heal, pos, &grid, &mut rng) == Some(ally)));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::prelude::*;
+use macroquad::audio::{
+    load_sound_from_bytes, play_sound, set_sound_volume, PlaySoundParams, Sound,
+};
+
+const VOLUME_STEP: f32 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SoundEffect {
+    CardPlayed,
+    Hit,
+    Death,
+    PhaseChange,
+    RoundStart,
+}
+
+impl SoundEffect {
+    pub fn all() -> [SoundEffect; 5] {
+        [
+            SoundEffect::CardPlayed,
+            SoundEffect::Hit,
+            SoundEffect::Death,
+            SoundEffect::PhaseChange,
+            SoundEffect::RoundStart,
+        ]
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            SoundEffect::CardPlayed => "sounds/card_played.wav",
+            SoundEffect::Hit => "sounds/hit.wav",
+            SoundEffect::Death => "sounds/death.wav",
+            SoundEffect::PhaseChange => "sounds/phase_change.wav",
+            SoundEffect::RoundStart => "sounds/round_start.wav",
+        }
+    }
+}
+
+pub fn get_combat_event_sound(event: &CombatEvent) -> Option<SoundEffect> {
+    match event {
+        CombatEvent::CardPlayed { .. } => Some(SoundEffect::CardPlayed),
+        CombatEvent::Damaged { .. } => Some(SoundEffect::Hit),
+        CombatEvent::Died { .. } => Some(SoundEffect::Death),
+        CombatEvent::CardResolved { .. }
+        | CombatEvent::Moved { .. }
+        | CombatEvent::Healed { .. } => None,
+    }
+}
+
+pub fn get_turn_event_sound(event: &TurnEvent) -> Option<SoundEffect> {
+    match event {
+        TurnEvent::RoundStarted(_) => Some(SoundEffect::RoundStart),
+        TurnEvent::PhaseChanged(TurnState::StartOfRound) => None,
+        TurnEvent::PhaseChanged(_) => Some(SoundEffect::PhaseChange),
+        _ => None,
+    }
+}
+
+// Volumes run from 0.0 to 1.0 and are saved as `name = value` lines, like the key bindings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub effects: f32,
+    pub music: f32,
+    pub muted: bool,
+}
+
+impl AudioSettings {
+    pub fn new() -> Self {
+        Self {
+            master: 0.8,
+            effects: 1.0,
+            music: 0.5,
+            muted: false,
+        }
+    }
+
+    pub fn load(path: &str) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(text) => parse_audio_settings(&text).unwrap_or_else(|err| {
+                eprintln!("Ignoring audio settings in {path}: {err}");
+                Self::new()
+            }),
+
+            Err(_) => Self::new(),
+        }
+    }
+
+    // The player's own settings if they've changed any, otherwise the shipped defaults.
+    pub fn load_or_defaults(path: &str, defaults_path: &str) -> Self {
+        match std::path::Path::new(path).exists() {
+            true => Self::load(path),
+            false => Self::load(defaults_path),
+        }
+    }
+
+    pub fn save(&self, path: &str) {
+        if let Err(err) = std::fs::write(path, self.to_config()) {
+            eprintln!("Failed to save audio settings to {path}: {err}");
+        }
+    }
+
+    fn to_config(self) -> String {
+        format!(
+            "# Audio volumes, from 0.0 to 1.0.\nmaster = {}\neffects = {}\nmusic = {}\nmuted = {}\n",
+            self.master, self.effects, self.music, self.muted
+        )
+    }
+
+    pub fn effects_volume(&self) -> f32 {
+        match self.muted {
+            true => 0.0,
+            false => self.master * self.effects,
+        }
+    }
+
+    pub fn music_volume(&self) -> f32 {
+        match self.muted {
+            true => 0.0,
+            false => self.master * self.music,
+        }
+    }
+
+    pub fn change_master(&mut self, steps: i32) {
+        self.master = (self.master + VOLUME_STEP * steps as f32).clamp(0.0, 1.0);
+    }
+}
+
+fn parse_audio_settings(text: &str) -> Result<AudioSettings, String> {
+    let mut settings = AudioSettings::new();
+
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .try_for_each(|line| {
+            let (name, value) = line
+                .split_once('=')
+                .ok_or(format!("Expected `setting = value` but got: {line}"))?;
+            let value = value.trim();
+
+            let parse_volume = |value: &str| {
+                value
+                    .parse::<f32>()
+                    .map(|volume| volume.clamp(0.0, 1.0))
+                    .map_err(|_| format!("Invalid volume: {value}"))
+            };
+
+            match name.trim() {
+                "master" => settings.master = parse_volume(value)?,
+                "effects" => settings.effects = parse_volume(value)?,
+                "music" => settings.music = parse_volume(value)?,
+                "muted" => {
+                    settings.muted = value
+                        .parse::<bool>()
+                        .map_err(|_| format!("Invalid muted flag: {value}"))?
+                }
+                other => return Err(format!("Unknown setting: {other}")),
+            }
+
+            Ok(())
+        })?;
+
+    Ok(settings)
+}
+
+enum AudioBackend {
+    // No audio device, e.g. when running headless. Everything is a no-op.
+    Silent,
+    Device {
+        effects: Vec<(SoundEffect, Sound)>,
+        music: Option<Sound>,
+    },
+}
+
+pub struct AudioManager {
+    pub settings: AudioSettings,
+    settings_path: String,
+    backend: AudioBackend,
+}
+
+impl AudioManager {
+    pub fn silent(settings: AudioSettings, settings_path: &str) -> Self {
+        Self {
+            settings,
+            settings_path: settings_path.to_string(),
+            backend: AudioBackend::Silent,
+        }
+    }
+
+    // Loads every sound effect and the battle music from `root`, skipping any that are missing.
+    pub async fn load(root: &str, settings: AudioSettings, settings_path: &str) -> Self {
+        let mut effects = Vec::new();
+        for effect in SoundEffect::all() {
+            if let Some(sound) = load_sound_file(&format!("{root}/{}", effect.file_name())).await {
+                effects.push((effect, sound));
+            }
+        }
+
+        let music = load_sound_file(&format!("{root}/music/battle.ogg")).await;
+        if let Some(music) = music {
+            play_sound(
+                music,
+                PlaySoundParams {
+                    looped: true,
+                    volume: settings.music_volume(),
+                },
+            );
+        }
+
+        Self {
+            settings,
+            settings_path: settings_path.to_string(),
+            backend: AudioBackend::Device { effects, music },
+        }
+    }
+
+    pub fn play(&mut self, effect: SoundEffect) {
+        if let AudioBackend::Device { effects, .. } = &self.backend {
+            if let Some((_, sound)) = effects.iter().find(|(loaded, _)| *loaded == effect) {
+                play_sound(
+                    *sound,
+                    PlaySoundParams {
+                        looped: false,
+                        volume: self.settings.effects_volume(),
+                    },
+                );
+            }
+        }
+    }
+
+    // Applies and saves changed settings.
+    pub fn update_settings(&mut self, settings: AudioSettings) {
+        self.settings = settings;
+        self.settings.save(&self.settings_path);
+
+        if let AudioBackend::Device {
+            music: Some(music), ..
+        } = &self.backend
+        {
+            set_sound_volume(*music, self.settings.music_volume());
+        }
+    }
+}
+
+async fn load_sound_file(path: &str) -> Option<Sound> {
+    let bytes = std::fs::read(path).ok()?;
+    load_sound_from_bytes(&bytes).await.ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_audio_settings() {
+        let text = "# Quiet\nmaster = 0.5\nmusic=2.0\nmuted = true\n";
+        let settings = parse_audio_settings(text).unwrap();
+
+        assert_eq!(settings.master, 0.5);
+        assert_eq!(settings.effects, 1.0);
+        assert_eq!(settings.music, 1.0);
+        assert!(settings.muted);
+        assert_eq!(settings.effects_volume(), 0.0);
+
+        assert!(parse_audio_settings("volume = 1").is_err());
+        assert!(parse_audio_settings("master = loud").is_err());
+    }
+
+    #[test]
+    fn test_audio_settings_round_trip() {
+        let mut settings = AudioSettings::new();
+        settings.change_master(-3);
+        settings.muted = true;
+
+        assert_eq!(parse_audio_settings(&settings.to_config()), Ok(settings));
+    }
+
+    #[test]
+    fn test_defaults_until_the_player_saves() {
+        let path = std::env::temp_dir().join("raid_night_audio_test.cfg");
+        let path = path.to_str().unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let defaults = AudioSettings::load("./assets/audio.cfg");
+        assert_eq!(
+            AudioSettings::load_or_defaults(path, "./assets/audio.cfg"),
+            defaults
+        );
+
+        let mut changed = defaults;
+        changed.muted = !changed.muted;
+        changed.save(path);
+        assert_eq!(
+            AudioSettings::load_or_defaults(path, "./assets/audio.cfg"),
+            changed
+        );
+        assert_eq!(AudioSettings::load("./assets/audio.cfg"), defaults);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_event_sounds() {
+        assert_eq!(
+            get_turn_event_sound(&TurnEvent::PhaseChanged(TurnState::ResolvePhase)),
+            Some(SoundEffect::PhaseChange)
+        );
+        assert_eq!(get_turn_event_sound(&TurnEvent::RoundEnded(1)), None);
+    }
+}
@@ -5,6 +5,20 @@ use std::io::{self, Write};
 pub enum TileStatus {
     Empty,
     Occupied,
+    // A piece that also shields whatever is behind it, like the Fighter.
+    Blocking,
+    // Scenery: nothing can stand here or see through it.
+    Obstacle,
+}
+
+impl TileStatus {
+    pub fn is_occupied(&self) -> bool {
+        matches!(self, TileStatus::Occupied | TileStatus::Blocking)
+    }
+
+    pub fn blocks_line_of_sight(&self) -> bool {
+        matches!(self, TileStatus::Blocking | TileStatus::Obstacle)
+    }
 }
 
 pub struct BattleGrid {
@@ -14,6 +28,7 @@ pub struct BattleGrid {
     pub grid_size: f32,
     pub line_width: f32,
     pub tile_statuses: Vec<TileStatus>,
+    pub hazards: Vec<Hazard>,
 }
 
 impl BattleGrid {
@@ -28,6 +43,7 @@ impl BattleGrid {
             grid_size: 96.,
             line_width: 4.,
             tile_statuses: t_statuses,
+            hazards: Vec::new(),
         }
     }
 
@@ -118,6 +134,43 @@ impl BattleGrid {
     pub fn is_cell_in_bounds(&self, coord: Coordinate) -> bool {
         coord.x >= 0 && coord.x < self.width && coord.y >= 0 && coord.y < self.height
     }
+
+    pub fn get_hazard_at(&self, coord: Coordinate) -> Option<&Hazard> {
+        self.hazards.iter().find(|hazard| hazard.coord == coord)
+    }
+
+    // A cell holds one hazard at a time, so the newest wins.
+    pub fn place_hazard(&mut self, coord: Coordinate, kind: HazardKind, rounds: i32) {
+        // Attacks on the dragon land off the grid, where there's nothing to leave a hazard on.
+        if !self.is_cell_in_bounds(coord) {
+            return;
+        }
+
+        self.hazards.retain(|hazard| hazard.coord != coord);
+        self.hazards.push(Hazard {
+            coord,
+            kind,
+            rounds_left: rounds,
+        });
+    }
+
+    pub fn tick_hazards(&mut self) {
+        self.hazards
+            .iter_mut()
+            .for_each(|hazard| hazard.rounds_left -= 1);
+        self.hazards.retain(|hazard| hazard.rounds_left > 0);
+    }
+
+    // The cells strictly between `from` and `to` that block line of sight, nearest `from` first.
+    // Either end may be off the grid, e.g. the dragon's origin.
+    pub fn get_cover_between(&self, from: Coordinate, to: Coordinate) -> Vec<Coordinate> {
+        from.line_to(to)
+            .into_iter()
+            .filter(|coord| *coord != from && *coord != to)
+            .filter(|coord| self.is_cell_in_bounds(*coord))
+            .filter(|coord| self.get_status_at_coord(coord).blocks_line_of_sight())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -164,4 +217,26 @@ mod tests {
         grid.set_status_at_coord(&test_coord, TileStatus::Empty);
         assert_eq!(grid.get_status_at_coord(&test_coord), TileStatus::Empty);
     }
+
+    #[test]
+    fn test_only_cover_blocks_line_of_sight() {
+        let mut grid = BattleGrid::new();
+        let from = Coordinate { x: 2, y: 5 };
+        let to = Coordinate { x: 2, y: 0 };
+
+        grid.set_status_at_coord(&Coordinate { x: 2, y: 3 }, TileStatus::Occupied);
+        assert!(grid.get_cover_between(from, to).is_empty());
+
+        grid.set_status_at_coord(&Coordinate { x: 2, y: 1 }, TileStatus::Blocking);
+        grid.set_status_at_coord(&Coordinate { x: 2, y: 2 }, TileStatus::Obstacle);
+        assert_eq!(
+            grid.get_cover_between(from, to),
+            vec![Coordinate { x: 2, y: 2 }, Coordinate { x: 2, y: 1 }]
+        );
+
+        // The cover itself can still be hit.
+        assert!(grid
+            .get_cover_between(from, Coordinate { x: 2, y: 2 })
+            .is_empty());
+    }
 }
@@ -16,7 +16,7 @@ impl CardDB {
         let mut stmt = self
             .connection
             .prepare(&format!(
-                "SELECT CardID, Name, Cost, Effects FROM Cards WHERE CardID={}",
+                "SELECT CardID, Name, Cost, Effects, Art, Keywords FROM Cards WHERE CardID={}",
                 id
             ))
             .unwrap();
@@ -28,6 +28,8 @@ impl CardDB {
                     name: row.get(1).unwrap(),
                     cost: row.get(2).unwrap(),
                     effects: row.get(3).unwrap(),
+                    art: row.get(4).unwrap(),
+                    keywords: row.get(5).unwrap(),
                 })
             })
             .unwrap();
@@ -35,11 +37,25 @@ impl CardDB {
         card_iter.nth(0).unwrap().unwrap()
     }
 
+    // Every card that can be played, which leaves out status cards.
+    pub fn get_playable_card_ids(&self) -> Vec<i32> {
+        let mut stmt = self
+            .connection
+            .prepare("SELECT CardID FROM Cards WHERE Cost IS NOT NULL ORDER BY CardID")
+            .unwrap();
+
+        let id_iter = stmt.query_map([], |row| row.get(0)).unwrap();
+
+        id_iter
+            .collect::<Result<Vec<i32>, rusqlite::Error>>()
+            .unwrap()
+    }
+
     pub fn draw_random(&mut self, count: i32) -> Result<Vec<CardData>, rusqlite::Error> {
         let mut stmt = self
             .connection
             .prepare(&format!(
-                "SELECT CardID, Name, Cost, Effects FROM Cards ORDER BY RANDOM() LIMIT {}",
+                "SELECT CardID, Name, Cost, Effects, Art, Keywords FROM Cards ORDER BY RANDOM() LIMIT {}",
                 count
             ))
             .unwrap();
@@ -51,6 +67,8 @@ impl CardDB {
                     name: row.get(1).unwrap(),
                     cost: row.get(2).unwrap(),
                     effects: row.get(3).unwrap(),
+                    art: row.get(4).unwrap(),
+                    keywords: row.get(5).unwrap(),
                 })
             })
             .unwrap();
@@ -65,105 +83,49 @@ pub struct CardData {
     pub name: String,
     cost: Option<i32>,
     effects: String,
+    // Path of the card's art within the assets folder.
+    art: Option<String>,
+    // Comma separated, see `parse_keywords`.
+    keywords: Option<String>,
 }
 
 impl CardData {
+    pub fn get_keywords(&self) -> Result<Vec<Keyword>, String> {
+        parse_keywords(self.keywords.as_deref().unwrap_or_default())
+    }
+
     pub fn spawn_as_entity(&self, commands: &mut CommandBuffer) -> Result<Entity, String> {
         let card_effects =
-            get_card_effects_from_text(self.effects.to_owned()).expect("Failed to parse Card Text");
+            get_effects_from_text(self.effects.to_owned()).expect("Failed to parse Card Text");
 
         let entity = commands.push((
             (),
             Card {
                 name: self.name.to_owned(),
             },
+            Effects { list: card_effects },
+            Keywords {
+                list: self.get_keywords()?,
+            },
         ));
 
         if let Some(cost) = self.cost {
             commands.add_component(entity, Cost { amount: cost });
         }
 
-        card_effects.iter().for_each(|effect| match effect {
-            _ => {
-                eprintln!(
-                    "Unimplemented Card Effect!\n{:?}\nIn Card: {}",
-                    effect, self.name
-                )
-            }
-        });
-
-        Ok(entity)
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-enum Target {
-    Vertical(i32),
-    Horizontal(i32),
-}
-
-#[derive(Debug, Clone, PartialEq)]
-enum CardEffect {
-    DealDamage(i32),
-    Block(i32),
-    DefineTarget(Target),
-    Unimplemented(String),
-}
-
-impl CardEffect {
-    fn from_string(val: String) -> Result<Self, String> {
-        if let Some(open_paren_pos) = val.find("(") {
-            if let Some(close_paren_pos) = val.find(")") {
-                if close_paren_pos > open_paren_pos {
-                    let keyword = val[0..open_paren_pos].to_string();
-                    let params_str = val[open_paren_pos + 1..close_paren_pos].to_string();
-
-                    let mut params = params_str.split(",").into_iter().map(|val| {
-                        val.trim()
-                            .parse::<i32>()
-                            .expect(&format!("Error extracting params from string: {val:?}"))
-                    });
-
-                    match keyword.as_str() {
-                        "deal" => return Ok(CardEffect::DealDamage(params.nth(0).unwrap())),
-                        "block" => return Ok(CardEffect::Block(params.nth(0).unwrap())),
-                        "vertical" => {
-                            return Ok(CardEffect::DefineTarget(Target::Vertical(
-                                params.nth(0).unwrap(),
-                            )))
-                        }
-
-                        "horizontal" => {
-                            return Ok(CardEffect::DefineTarget(Target::Horizontal(
-                                params.nth(0).unwrap(),
-                            )))
-                        }
-
-                        "target_bottom_rows" => {
-                            return Ok(CardEffect::Unimplemented("TargetBottomRows".to_string()));
-                        }
-
-                        _ => return Err(format!("could not parse command: {}", keyword)),
-                    }
-                }
-            }
+        if let Some(art) = &self.art {
+            commands.add_component(
+                entity,
+                Sprite {
+                    path: art.to_owned(),
+                },
+            );
         }
 
-        Err(format!("Could not successfully parse string: {}", val))
+        Ok(entity)
     }
 }
 
-fn get_card_effects_from_text(val: String) -> Result<Vec<CardEffect>, String> {
-    let effects = val
-        .split(';')
-        .map(|command| command.trim())
-        .filter(|command| *command != "")
-        .map(|command| CardEffect::from_string(command.to_string()))
-        .collect::<Result<Vec<CardEffect>, String>>();
-
-    effects
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,33 +139,11 @@ mod tests {
             id: 1,
             name: "Firey Breath".to_string(),
             cost: Some(1),
-            effects: "vertical(1);\ndeal(1);".to_string(),
+            effects: "vertical(1);\ndeal(1);\nfire(2);".to_string(),
+            art: Some("cards/firey_breath.png".to_string()),
+            keywords: None,
         };
 
         assert_eq!(actual, expected)
     }
-
-    #[test]
-    fn test_deal_damage_string() {
-        let amount = 4;
-        let effect_string = format!("deal({})", amount);
-        let actual = CardEffect::from_string(effect_string).unwrap();
-
-        let expected = CardEffect::DealDamage(amount);
-        assert_eq!(actual, expected)
-    }
-
-    #[test]
-    fn test_multiple_commands_in_string() {
-        let expected = Ok(vec![
-            CardEffect::DealDamage(3),
-            CardEffect::Block(4),
-            CardEffect::DefineTarget(Target::Horizontal(1)),
-        ]);
-        let effect_string = "deal(3);block(4)\n;\nhorizontal(1);";
-
-        let actual = get_card_effects_from_text(effect_string.to_string());
-
-        assert_eq!(actual, expected)
-    }
 }
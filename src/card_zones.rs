@@ -1,9 +1,61 @@
 use crate::prelude::*;
 
+// How many cards the player holds after drawing at the start of each round.
+pub const HAND_SIZE: usize = 5;
+
+// Rules a card can carry that change how it moves between zones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Keyword {
+    // Removed for the rest of the battle once played.
+    Exhaust,
+    // Kept in hand when the round ends.
+    Retain,
+    // Always starts in the opening hand.
+    Innate,
+    // Exhausted if it's still in hand when the round ends.
+    Ethereal,
+}
+
+impl Keyword {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Keyword::Exhaust => "Exhaust",
+            Keyword::Retain => "Retain",
+            Keyword::Innate => "Innate",
+            Keyword::Ethereal => "Ethereal",
+        }
+    }
+}
+
+// Keywords are stored as a comma separated list, e.g. `retain, ethereal`.
+pub fn parse_keywords(val: &str) -> Result<Vec<Keyword>, String> {
+    val.split(',')
+        .map(|keyword| keyword.trim())
+        .filter(|keyword| !keyword.is_empty())
+        .map(|keyword| match keyword.to_lowercase().as_str() {
+            "exhaust" => Ok(Keyword::Exhaust),
+            "retain" => Ok(Keyword::Retain),
+            "innate" => Ok(Keyword::Innate),
+            "ethereal" => Ok(Keyword::Ethereal),
+            other => Err(format!("Unknown card keyword: {other}")),
+        })
+        .collect()
+}
+
+// The top of the deck is the end of `deck`.
 pub struct CardZones {
     pub deck: Vec<Entity>,
     pub discard: Vec<Entity>,
     pub hand: Vec<Entity>,
+    // Cards removed for the rest of the battle.
+    pub exhaust: Vec<Entity>,
+    // Cards taken off the top of the deck by a scry, waiting for the player to reorder or discard
+    // them. The first one goes back on top.
+    pub scrying: Vec<Entity>,
+    // Hand changes made while the player couldn't use the hand, e.g. a draw from a card that
+    // resolves after the player has declared. They wait for the next declare phase, so they act
+    // on the hand the player actually plays from.
+    pub pending_changes: Vec<CardChange>,
 }
 
 impl CardZones {
@@ -12,6 +64,282 @@ impl CardZones {
             deck: Vec::new(),
             discard: Vec::new(),
             hand: Vec::new(),
+            exhaust: Vec::new(),
+            scrying: Vec::new(),
+            pending_changes: Vec::new(),
+        }
+    }
+
+    // Shuffles the cards into the deck, with the innate ones on top so they're drawn first.
+    pub fn build_deck(&mut self, cards: &[(Entity, Vec<Keyword>)], rng: &mut StdRng) {
+        let (mut innate, mut rest): (Vec<Entity>, Vec<Entity>) = (Vec::new(), Vec::new());
+        cards.iter().for_each(
+            |(card, keywords)| match keywords.contains(&Keyword::Innate) {
+                true => innate.push(*card),
+                false => rest.push(*card),
+            },
+        );
+
+        rest.shuffle(rng);
+        innate.shuffle(rng);
+        self.deck = rest;
+        self.deck.extend(innate);
+    }
+
+    // Draws cards from the top of the deck, shuffling the discard pile back in when it runs out.
+    pub fn draw(&mut self, count: usize, rng: &mut StdRng) {
+        for _ in 0..count {
+            if self.deck.is_empty() {
+                self.deck = std::mem::take(&mut self.discard);
+                self.deck.shuffle(rng);
+            }
+
+            match self.deck.pop() {
+                Some(card) => self.hand.push(card),
+                None => break,
+            }
+        }
+    }
+
+    // Moves a played card out of the hand. Returns false if it had already left.
+    pub fn play(&mut self, card: Entity, keywords: &[Keyword]) -> bool {
+        match self.hand.iter().position(|held| *held == card) {
+            Some(idx) => {
+                self.hand.remove(idx);
+                match keywords.contains(&Keyword::Exhaust) {
+                    true => self.exhaust.push(card),
+                    false => self.discard.push(card),
+                }
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    // Discards cards at random, leaving alone any in `keep`, such as cards already queued to play.
+    pub fn discard_random(&mut self, count: usize, keep: &[Entity], rng: &mut StdRng) {
+        for _ in 0..count {
+            let candidates = self
+                .hand
+                .iter()
+                .enumerate()
+                .filter(|(_, card)| !keep.contains(card))
+                .map(|(idx, _)| idx)
+                .collect::<Vec<usize>>();
+
+            match candidates.choose(rng) {
+                Some(idx) => {
+                    let card = self.hand.remove(*idx);
+                    self.discard.push(card);
+                }
+                None => break,
+            }
+        }
+    }
+
+    // Slips the card into the deck at a random depth.
+    pub fn shuffle_into_deck(&mut self, card: Entity, rng: &mut StdRng) {
+        let idx = rng.gen_range(0..=self.deck.len());
+        self.deck.insert(idx, card);
+    }
+
+    // Swaps a card in hand for another. Returns false if the old card isn't in hand.
+    pub fn replace_in_hand(&mut self, old: Entity, new: Entity) -> bool {
+        match self.hand.iter_mut().find(|held| **held == old) {
+            Some(held) => {
+                *held = new;
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    // Takes up to `count` cards off the top of the deck to scry.
+    pub fn start_scry(&mut self, count: usize) {
+        let split = self.deck.len().saturating_sub(count);
+        self.scrying.extend(self.deck.drain(split..).rev());
+    }
+
+    // Moves a scried card to the front, so it goes back on top.
+    pub fn scry_to_top(&mut self, idx: usize) {
+        if idx < self.scrying.len() {
+            let card = self.scrying.remove(idx);
+            self.scrying.insert(0, card);
+        }
+    }
+
+    pub fn scry_to_discard(&mut self, idx: usize) {
+        if idx < self.scrying.len() {
+            let card = self.scrying.remove(idx);
+            self.discard.push(card);
         }
     }
+
+    // Puts what's left of the scry back on the deck, first card on top.
+    pub fn finish_scry(&mut self) {
+        let scried = std::mem::take(&mut self.scrying);
+        self.deck.extend(scried.into_iter().rev());
+    }
+
+    // Clears the hand at the end of a round, keeping retained cards and exhausting ethereal ones.
+    pub fn end_round(&mut self, keywords_of: impl Fn(&Entity) -> Vec<Keyword>) {
+        let hand = std::mem::take(&mut self.hand);
+        hand.into_iter().for_each(|card| {
+            let keywords = keywords_of(&card);
+            if keywords.contains(&Keyword::Ethereal) {
+                self.exhaust.push(card);
+            } else if keywords.contains(&Keyword::Retain) {
+                self.hand.push(card);
+            } else {
+                self.discard.push(card);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(world: &mut World, count: usize) -> Vec<Entity> {
+        (0..count).map(|_| world.push(())).collect()
+    }
+
+    #[test]
+    fn test_parse_keywords() {
+        assert_eq!(
+            parse_keywords("Retain, ethereal"),
+            Ok(vec![Keyword::Retain, Keyword::Ethereal])
+        );
+        assert_eq!(parse_keywords(""), Ok(Vec::new()));
+        assert!(parse_keywords("sticky").is_err());
+    }
+
+    #[test]
+    fn test_innate_cards_are_drawn_first() {
+        let mut world = World::default();
+        let entities = cards(&mut world, 6);
+        let mut rng = StdRng::seed_from_u64(3);
+        let deck = entities
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| match idx {
+                4 => (*card, vec![Keyword::Innate]),
+                _ => (*card, Vec::new()),
+            })
+            .collect::<Vec<(Entity, Vec<Keyword>)>>();
+
+        let mut zones = CardZones::new();
+        zones.build_deck(&deck, &mut rng);
+        zones.draw(1, &mut rng);
+
+        assert_eq!(zones.hand, vec![entities[4]]);
+        assert_eq!(zones.deck.len(), 5);
+    }
+
+    #[test]
+    fn test_discard_is_reshuffled_into_the_deck() {
+        let mut world = World::default();
+        let entities = cards(&mut world, 3);
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let mut zones = CardZones::new();
+        zones.deck = vec![entities[0]];
+        zones.discard = vec![entities[1], entities[2]];
+        zones.draw(3, &mut rng);
+
+        assert_eq!(zones.hand.len(), 3);
+        assert!(zones.deck.is_empty() && zones.discard.is_empty());
+
+        // Nothing left anywhere, so drawing stops early.
+        zones.draw(2, &mut rng);
+        assert_eq!(zones.hand.len(), 3);
+    }
+
+    #[test]
+    fn test_keywords_decide_where_cards_go() {
+        let mut world = World::default();
+        let entities = cards(&mut world, 4);
+        let mut zones = CardZones::new();
+        zones.hand = entities.clone();
+
+        assert!(zones.play(entities[0], &[Keyword::Exhaust]));
+        assert!(!zones.play(entities[0], &[]));
+        assert_eq!(zones.exhaust, vec![entities[0]]);
+
+        let retained = entities[1];
+        let ethereal = entities[2];
+        zones.end_round(|card| match *card {
+            card if card == retained => vec![Keyword::Retain],
+            card if card == ethereal => vec![Keyword::Ethereal],
+            _ => Vec::new(),
+        });
+
+        assert_eq!(zones.hand, vec![entities[1]]);
+        assert_eq!(zones.exhaust, vec![entities[0], entities[2]]);
+        assert_eq!(zones.discard, vec![entities[3]]);
+    }
+
+    #[test]
+    fn test_discard_skips_kept_cards() {
+        let mut world = World::default();
+        let entities = cards(&mut world, 3);
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut zones = CardZones::new();
+        zones.hand = entities.clone();
+
+        zones.discard_random(5, &[entities[1]], &mut rng);
+
+        assert_eq!(zones.hand, vec![entities[1]]);
+        assert_eq!(zones.discard.len(), 2);
+    }
+
+    #[test]
+    fn test_deferred_draw_lands_in_the_next_hand() {
+        let mut world = World::default();
+        let entities = cards(&mut world, HAND_SIZE * 2);
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut zones = CardZones::new();
+        zones.deck = entities[HAND_SIZE..].to_vec();
+        zones.hand = entities[..HAND_SIZE].to_vec();
+
+        // The card resolves after the player has declared, so its draw waits for the new hand.
+        assert!(zones.play(entities[0], &[]));
+        zones.pending_changes.push(CardChange::Draw(1));
+
+        zones.end_round(|_| Vec::new());
+        zones.draw(HAND_SIZE.saturating_sub(zones.hand.len()), &mut rng);
+        std::mem::take(&mut zones.pending_changes)
+            .iter()
+            .for_each(|change| {
+                if let CardChange::Draw(count) = change {
+                    zones.draw(*count as usize, &mut rng);
+                }
+            });
+
+        assert_eq!(zones.hand.len(), HAND_SIZE + 1);
+        assert!(zones.hand.iter().all(|card| !zones.discard.contains(card)));
+    }
+
+    #[test]
+    fn test_scry_reorders_the_top_of_the_deck() {
+        let mut world = World::default();
+        let entities = cards(&mut world, 4);
+        let mut zones = CardZones::new();
+        zones.deck = entities.clone();
+
+        // The top three, in draw order.
+        zones.start_scry(3);
+        assert_eq!(zones.scrying, vec![entities[3], entities[2], entities[1]]);
+
+        zones.scry_to_top(2);
+        zones.scry_to_discard(1);
+        zones.finish_scry();
+
+        assert_eq!(zones.deck, vec![entities[0], entities[2], entities[1]]);
+        assert_eq!(zones.discard, vec![entities[3]]);
+        assert!(zones.scrying.is_empty());
+    }
 }
@@ -0,0 +1,60 @@
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CombatEvent {
+    CardPlayed {
+        entity: Entity,
+        card: Entity,
+    },
+    // A played card has left the hand and taken effect.
+    CardResolved {
+        card: Entity,
+    },
+    // Every cell the entity passed through, starting with where it stood.
+    Moved {
+        entity: Entity,
+        path: Vec<Coordinate>,
+    },
+    Damaged {
+        entity: Entity,
+        coord: Coordinate,
+        amount: i32,
+    },
+    Healed {
+        entity: Entity,
+        coord: Coordinate,
+        amount: i32,
+    },
+    Died {
+        entity: Entity,
+        coord: Coordinate,
+    },
+}
+
+// Raised by the resolve systems for animation, sound and UI to react to. Works like the turn
+// tracker's events: whatever is pushed during a frame is readable for the whole next frame.
+pub struct CombatEvents {
+    pending: Vec<CombatEvent>,
+    published: Vec<CombatEvent>,
+}
+
+impl CombatEvents {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            published: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, event: CombatEvent) {
+        self.pending.push(event);
+    }
+
+    pub fn publish(&mut self) {
+        self.published = std::mem::take(&mut self.pending);
+    }
+
+    pub fn events(&self) -> &[CombatEvent] {
+        &self.published
+    }
+}
@@ -1,3 +1,5 @@
+use crate::prelude::*;
+
 pub struct Card {
     pub name: String,
 }
@@ -5,3 +7,19 @@ pub struct Card {
 pub struct Cost {
     pub amount: i32,
 }
+
+pub struct Keywords {
+    pub list: Vec<Keyword>,
+}
+
+pub struct PlayCard {
+    pub card: Entity,
+    // Position in the player's declaration queue, so the last play can be undone.
+    pub order: usize,
+    pub target: Coordinate,
+}
+
+// Card changes a hero's ability makes to the player's zones.
+pub struct CardChanges {
+    pub list: Vec<CardChange>,
+}
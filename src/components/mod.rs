@@ -7,6 +7,9 @@ pub struct Player;
 
 pub struct Enemy;
 
+// Scenery that takes up a cell and blocks line of sight.
+pub struct Obstacle;
+
 pub struct Initiative {
     pub init_mod: i32,
     pub priority: i32,
@@ -16,6 +19,17 @@ pub struct Name {
     pub val: String,
 }
 
+pub struct Health {
+    pub current: i32,
+    pub max: i32,
+}
+
+impl Health {
+    pub fn new(max: i32) -> Self {
+        Self { current: max, max }
+    }
+}
+
 pub struct ActionPoints {
     pub current: i32,
     pub max: i32,
@@ -40,3 +54,56 @@ pub struct Round;
 pub struct Move {
     pub dirs: Vec<Direction>,
 }
+
+pub struct Attack {
+    pub targets: Vec<Coordinate>,
+    pub damage: i32,
+}
+
+// Shoves whatever stands on the target cells away from `origin`, or towards it when `distance`
+// is negative.
+pub struct ForcedMove {
+    pub targets: Vec<Coordinate>,
+    pub origin: Coordinate,
+    pub distance: i32,
+}
+
+// Leaves hazards on the target cells.
+pub struct PlaceHazards {
+    pub targets: Vec<Coordinate>,
+    pub hazards: Vec<(HazardKind, i32)>,
+}
+
+// Effects a card or hero ability applies, see `interpret_effects`.
+pub struct Effects {
+    pub list: Vec<Effect>,
+}
+
+pub struct Movement {
+    pub max: i32,
+    // Whether diagonal steps are allowed as well as straight ones.
+    pub diagonal: bool,
+}
+
+pub struct Abilities {
+    pub list: Vec<Ability>,
+}
+
+// The stages a champion goes through as it's worn down, and how many it has reached so far.
+pub struct Phases {
+    pub list: Vec<HeroPhase>,
+    pub reached: usize,
+}
+
+// Draws the dragon's attacks onto this hero.
+pub struct Taunt;
+
+pub struct Sprite {
+    pub path: String,
+}
+
+// Delays or hastens whoever stands on the target cells, or the dragon if its origin is hit.
+pub struct InitiativeChange {
+    pub targets: Vec<Coordinate>,
+    pub changes: Vec<(i32, InitiativeTiming)>,
+}
@@ -1,7 +1,7 @@
 use crate::prelude::*;
 use std::ops::{Add, Mul, Sub, SubAssign};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Coordinate {
     pub x: i32,
     pub y: i32,
@@ -11,6 +11,79 @@ impl Coordinate {
     const X: Self = Self { x: 1, y: 0 };
 
     const Y: Self = Self { x: 0, y: 1 };
+
+    // Steps needed when moving only in straight lines.
+    pub fn manhattan_distance(&self, other: Coordinate) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    // Steps needed when diagonal moves are allowed.
+    pub fn chebyshev_distance(&self, other: Coordinate) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    pub fn neighbours(&self) -> impl Iterator<Item = Coordinate> {
+        let center = *self;
+        Direction::all()
+            .into_iter()
+            .map(move |dir| center + Coordinate::from(dir))
+    }
+
+    pub fn neighbours_with_diagonals(&self) -> impl Iterator<Item = Coordinate> {
+        let center = *self;
+        Direction::all_with_diagonals()
+            .into_iter()
+            .map(move |dir| center + Coordinate::from(dir))
+    }
+
+    // A quarter turn clockwise around the origin, with y pointing down the screen.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    // Treats this as an offset drawn facing up and turns it to face `facing`. Diagonal facings
+    // turn as far as the straight direction just counter-clockwise of them.
+    pub fn rotate_to_face(&self, facing: Direction) -> Self {
+        let turns = match facing {
+            Direction::Up | Direction::UpRight => 0,
+            Direction::Right | Direction::DownRight => 1,
+            Direction::Down | Direction::DownLeft => 2,
+            Direction::Left | Direction::UpLeft => 3,
+        };
+
+        (0..turns).fold(*self, |coord, _| coord.rotate_clockwise())
+    }
+
+    // Every cell a straight line between the two cell centers passes through, both ends
+    // included, for line of sight checks.
+    pub fn line_to(&self, other: Coordinate) -> Vec<Coordinate> {
+        let dx = (other.x - self.x).abs();
+        let dy = -(other.y - self.y).abs();
+        let step_x = (other.x - self.x).signum();
+        let step_y = (other.y - self.y).signum();
+
+        let mut cells = vec![*self];
+        let mut current = *self;
+        let mut error = dx + dy;
+
+        while current != other {
+            let doubled = error * 2;
+            if doubled >= dy {
+                error += dy;
+                current.x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                current.y += step_y;
+            }
+            cells.push(current);
+        }
+
+        cells
+    }
 }
 
 impl Sub for Coordinate {
@@ -61,6 +134,77 @@ impl From<Direction> for Coordinate {
             Direction::Down => Self::Y,
             Direction::Right => Self::X,
             Direction::Left => Self::X * -1,
+            Direction::UpRight => Self::X - Self::Y,
+            Direction::DownRight => Self::X + Self::Y,
+            Direction::DownLeft => Self::Y - Self::X,
+            Direction::UpLeft => (Self::X + Self::Y) * -1,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_distances() {
+        let a = Coordinate { x: 1, y: 1 };
+        let b = Coordinate { x: 4, y: 3 };
+
+        assert_eq!(a.manhattan_distance(b), 5);
+        assert_eq!(a.chebyshev_distance(b), 3);
+        assert_eq!(b.manhattan_distance(a), 5);
+    }
+
+    #[test]
+    fn test_neighbours_are_unique_and_adjacent() {
+        let center = Coordinate { x: 2, y: 2 };
+        let cardinal = center.neighbours().collect::<HashSet<Coordinate>>();
+        let all = center
+            .neighbours_with_diagonals()
+            .collect::<HashSet<Coordinate>>();
+
+        assert_eq!(cardinal.len(), 4);
+        assert_eq!(all.len(), 8);
+        assert!(cardinal.is_subset(&all));
+        assert!(all
+            .iter()
+            .all(|coord| center.chebyshev_distance(*coord) == 1));
+    }
+
+    #[test]
+    fn test_rotate_to_face() {
+        let ahead = Coordinate::from(Direction::Up);
+
+        Direction::all().iter().for_each(|dir| {
+            assert_eq!(ahead.rotate_to_face(*dir), Coordinate::from(*dir));
+        });
+        assert_eq!(
+            Coordinate::from(Direction::UpRight).rotate_to_face(Direction::Down),
+            Coordinate::from(Direction::DownLeft)
+        );
+    }
+
+    #[test]
+    fn test_line_to() {
+        let start = Coordinate { x: 0, y: 0 };
+
+        assert_eq!(start.line_to(start), vec![start]);
+        assert_eq!(
+            start.line_to(Coordinate { x: 3, y: 1 }),
+            vec![
+                start,
+                Coordinate { x: 1, y: 0 },
+                Coordinate { x: 2, y: 1 },
+                Coordinate { x: 3, y: 1 },
+            ]
+        );
+        assert_eq!(
+            Coordinate { x: 2, y: 4 }
+                .line_to(Coordinate { x: 2, y: 1 })
+                .len(),
+            4
+        );
+    }
+}
@@ -1,9 +1,13 @@
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum Direction {
     Up,
     Right,
     Down,
     Left,
+    UpRight,
+    DownRight,
+    DownLeft,
+    UpLeft,
 }
 
 impl Direction {
@@ -16,12 +20,75 @@ impl Direction {
         ]
     }
 
+    // Clockwise from up, for pieces that are allowed to move diagonally.
+    pub fn all_with_diagonals() -> [Direction; 8] {
+        [
+            Direction::Up,
+            Direction::UpRight,
+            Direction::Right,
+            Direction::DownRight,
+            Direction::Down,
+            Direction::DownLeft,
+            Direction::Left,
+            Direction::UpLeft,
+        ]
+    }
+
     pub fn reverse(&self) -> Self {
+        self.rotate_clockwise().rotate_clockwise()
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        matches!(
+            self,
+            Direction::UpRight | Direction::DownRight | Direction::DownLeft | Direction::UpLeft
+        )
+    }
+
+    // A quarter turn.
+    pub fn rotate_clockwise(&self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+            Direction::UpRight => Direction::DownRight,
+            Direction::DownRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpLeft,
+            Direction::UpLeft => Direction::UpRight,
+        }
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
         match self {
-            Direction::Up => Direction::Down,
-            Direction::Right => Direction::Left,
-            Direction::Down => Direction::Up,
-            Direction::Left => Direction::Right,
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+            Direction::UpRight => Direction::UpLeft,
+            Direction::UpLeft => Direction::DownLeft,
+            Direction::DownLeft => Direction::DownRight,
+            Direction::DownRight => Direction::UpRight,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_round_trips() {
+        Direction::all_with_diagonals().iter().for_each(|dir| {
+            assert_eq!(dir.rotate_clockwise().rotate_counter_clockwise(), *dir);
+            assert_eq!(dir.rotate_counter_clockwise().rotate_clockwise(), *dir);
+            assert_eq!(dir.reverse().reverse(), *dir);
+            assert_ne!(dir.reverse(), *dir);
+            assert_eq!(dir.reverse().is_diagonal(), dir.is_diagonal());
+        });
+
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction::UpLeft.rotate_clockwise(), Direction::UpRight);
+        assert_eq!(Direction::Up.rotate_counter_clockwise(), Direction::Left);
+    }
+}
@@ -0,0 +1,557 @@
+use crate::prelude::*;
+
+// Effects that move cards between the player's zones. Card ids refer to `CardDB`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CardChange {
+    Draw(i32),
+    // Discards cards from the hand at random.
+    Discard(i32),
+    // Adds a copy of the card to the hand that's exhausted once played or at the end of the round.
+    CreateTemporary(i32),
+    // Shuffles the card into the deck, e.g. a status card from a hero.
+    AddToDeck(i32),
+    // Turns this many cards in hand into random ones.
+    Transform(i32),
+    // Lets the player reorder or discard the top cards of the deck.
+    Scry(i32),
+}
+
+impl CardChange {
+    // Changes that work on the cards in hand, rather than the deck.
+    pub fn acts_on_hand(&self) -> bool {
+        match self {
+            CardChange::Draw(_)
+            | CardChange::Discard(_)
+            | CardChange::CreateTemporary(_)
+            | CardChange::Transform(_) => true,
+
+            CardChange::AddToDeck(_) | CardChange::Scry(_) => false,
+        }
+    }
+}
+
+// The effect language shared by cards and hero abilities. Effects are written as
+// `keyword(params);` statements, e.g. `vertical(1); deal(2);`.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Effect {
+    DealDamage(i32),
+    Block(i32),
+    DefineTarget(TargetShape),
+    // Passes through this many pieces of cover.
+    Pierce(i32),
+    // Stops at the first piece it reaches.
+    FirstInLine,
+    // Shoves hit pieces this many cells away from the caster, or towards it.
+    Push(i32),
+    Pull(i32),
+    // Leaves a hazard on every hit cell for this many rounds.
+    CreateHazard(HazardKind, i32),
+    ChangeCards(CardChange),
+    // Delays (negative) or hastens (positive) whoever is hit, this round or the next.
+    ChangeInitiative(i32, InitiativeTiming),
+}
+
+impl Effect {
+    pub fn from_string(val: String) -> Result<Self, String> {
+        if let Some(open_paren_pos) = val.find("(") {
+            if let Some(close_paren_pos) = val.find(")") {
+                if close_paren_pos > open_paren_pos {
+                    let keyword = val[0..open_paren_pos].to_string();
+                    let params_str = val[open_paren_pos + 1..close_paren_pos].to_string();
+
+                    let mut params = params_str.split(",").into_iter().map(|val| {
+                        val.trim()
+                            .parse::<i32>()
+                            .expect(&format!("Error extracting params from string: {val:?}"))
+                    });
+
+                    match keyword.as_str() {
+                        "deal" => return Ok(Effect::DealDamage(params.nth(0).unwrap())),
+                        "block" => return Ok(Effect::Block(params.nth(0).unwrap())),
+                        "single" => return Ok(Effect::DefineTarget(TargetShape::Single)),
+                        "vertical" => {
+                            return Ok(Effect::DefineTarget(TargetShape::Vertical(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "horizontal" => {
+                            return Ok(Effect::DefineTarget(TargetShape::Horizontal(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "radius" => {
+                            return Ok(Effect::DefineTarget(TargetShape::Radius(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "cone" => {
+                            return Ok(Effect::DefineTarget(TargetShape::Cone(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "line" => {
+                            return Ok(Effect::DefineTarget(TargetShape::Line(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "rows" => {
+                            return Ok(Effect::DefineTarget(TargetShape::Rows {
+                                first: params.next().unwrap(),
+                                last: params.next().unwrap(),
+                            }))
+                        }
+
+                        "target_bottom_rows" => {
+                            return Ok(Effect::DefineTarget(TargetShape::BottomRows(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "chain" => {
+                            return Ok(Effect::DefineTarget(TargetShape::Chain {
+                                bounces: params.next().unwrap(),
+                                range: params.next().unwrap(),
+                            }))
+                        }
+
+                        "pierce" => return Ok(Effect::Pierce(params.nth(0).unwrap())),
+                        "first" => return Ok(Effect::FirstInLine),
+                        "push" => return Ok(Effect::Push(params.nth(0).unwrap())),
+                        "pull" => return Ok(Effect::Pull(params.nth(0).unwrap())),
+                        "fire" => {
+                            return Ok(Effect::CreateHazard(
+                                HazardKind::Fire,
+                                params.nth(0).unwrap(),
+                            ))
+                        }
+
+                        "healing" => {
+                            return Ok(Effect::CreateHazard(
+                                HazardKind::Healing,
+                                params.nth(0).unwrap(),
+                            ))
+                        }
+
+                        "ice" => {
+                            return Ok(Effect::CreateHazard(
+                                HazardKind::Ice,
+                                params.nth(0).unwrap(),
+                            ))
+                        }
+
+                        "delay" => {
+                            return Ok(Effect::ChangeInitiative(
+                                -params.nth(0).unwrap(),
+                                InitiativeTiming::CurrentRound,
+                            ))
+                        }
+
+                        "haste" => {
+                            return Ok(Effect::ChangeInitiative(
+                                params.nth(0).unwrap(),
+                                InitiativeTiming::CurrentRound,
+                            ))
+                        }
+
+                        "delay_next" => {
+                            return Ok(Effect::ChangeInitiative(
+                                -params.nth(0).unwrap(),
+                                InitiativeTiming::NextRound,
+                            ))
+                        }
+
+                        "haste_next" => {
+                            return Ok(Effect::ChangeInitiative(
+                                params.nth(0).unwrap(),
+                                InitiativeTiming::NextRound,
+                            ))
+                        }
+
+                        "draw" => {
+                            return Ok(Effect::ChangeCards(CardChange::Draw(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "discard" => {
+                            return Ok(Effect::ChangeCards(CardChange::Discard(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "create" => {
+                            return Ok(Effect::ChangeCards(CardChange::CreateTemporary(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "add_to_deck" => {
+                            return Ok(Effect::ChangeCards(CardChange::AddToDeck(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "transform" => {
+                            return Ok(Effect::ChangeCards(CardChange::Transform(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        "scry" => {
+                            return Ok(Effect::ChangeCards(CardChange::Scry(
+                                params.nth(0).unwrap(),
+                            )))
+                        }
+
+                        _ => return Err(format!("could not parse command: {}", keyword)),
+                    }
+                }
+            }
+        }
+
+        Err(format!("Could not successfully parse string: {}", val))
+    }
+}
+
+pub fn get_effects_from_text(val: String) -> Result<Vec<Effect>, String> {
+    let effects = val
+        .split(';')
+        .map(|command| command.trim())
+        .filter(|command| *command != "")
+        .map(|command| Effect::from_string(command.to_string()))
+        .collect::<Result<Vec<Effect>, String>>();
+
+    effects
+}
+
+// What a list of effects adds up to once it's aimed at a cell.
+#[derive(Debug, Default, PartialEq)]
+pub struct EffectOutcome {
+    pub targets: Vec<Coordinate>,
+    pub damage: i32,
+    pub block: i32,
+    // Cells to shove hit pieces away from the caster; negative pulls them in.
+    pub forced_move: i32,
+    // Hazards to leave on the targets, with how many rounds each lasts.
+    pub hazards: Vec<(HazardKind, i32)>,
+    pub card_changes: Vec<CardChange>,
+    // Initiative changes for whoever is hit, see `InitiativeChange`.
+    pub initiative_changes: Vec<(i32, InitiativeTiming)>,
+}
+
+// Resolves effects cast from `caster` and aimed at `aim`. Without a target shape only the aimed
+// cell is hit; with several, everything any of them covers is hit. Cells behind cover are
+// dropped unless the effects pierce far enough.
+pub fn interpret_effects(
+    effects: &[Effect],
+    caster: Coordinate,
+    aim: Coordinate,
+    grid: &BattleGrid,
+) -> EffectOutcome {
+    let mut outcome = EffectOutcome::default();
+    let mut shapes = Vec::new();
+    let mut pierce = 0;
+    let mut first_in_line = false;
+
+    effects.iter().for_each(|effect| match effect {
+        Effect::DealDamage(amount) => outcome.damage += amount,
+        Effect::Block(amount) => outcome.block += amount,
+        Effect::DefineTarget(shape) => shapes.push(shape),
+        Effect::Pierce(amount) => pierce += amount,
+        Effect::FirstInLine => first_in_line = true,
+        Effect::Push(amount) => outcome.forced_move += amount,
+        Effect::Pull(amount) => outcome.forced_move -= amount,
+        Effect::CreateHazard(kind, rounds) => outcome.hazards.push((*kind, *rounds)),
+        Effect::ChangeCards(change) => outcome.card_changes.push(*change),
+        Effect::ChangeInitiative(amount, timing) => {
+            outcome.initiative_changes.push((*amount, *timing))
+        }
+    });
+
+    if shapes.is_empty() {
+        shapes.push(&TargetShape::Single);
+    }
+
+    shapes.iter().for_each(|shape| {
+        get_affected_cells(shape, caster, aim, grid)
+            .into_iter()
+            .filter(|coord| {
+                !shape.is_blocked_by_cover()
+                    || grid.get_cover_between(caster, *coord).len() <= pierce.max(0) as usize
+            })
+            .for_each(|coord| {
+                if !outcome.targets.contains(&coord) {
+                    outcome.targets.push(coord);
+                }
+            })
+    });
+
+    if first_in_line {
+        let nearest_piece = outcome
+            .targets
+            .iter()
+            .filter(|coord| match grid.is_cell_in_bounds(**coord) {
+                true => grid.get_status_at_coord(coord).is_occupied(),
+                // The only cell off the grid a shape can reach is the dragon's.
+                false => true,
+            })
+            .map(|coord| coord.chebyshev_distance(caster))
+            .min();
+
+        if let Some(nearest) = nearest_piece {
+            outcome
+                .targets
+                .retain(|coord| coord.chebyshev_distance(caster) <= nearest);
+        }
+    }
+
+    outcome
+}
+
+// Damaging effects that miss every taunting hero are re-aimed at the closest taunter they could
+// hit instead. Anything that can't reach a taunter is left where it was aimed.
+pub fn get_taunted_aim(
+    effects: &[Effect],
+    caster: Coordinate,
+    aim: Coordinate,
+    taunters: &[Coordinate],
+    grid: &BattleGrid,
+) -> Coordinate {
+    let hits_taunter = |aim: Coordinate| {
+        let outcome = interpret_effects(effects, caster, aim, grid);
+        outcome.damage > 0 && taunters.iter().any(|coord| outcome.targets.contains(coord))
+    };
+
+    if interpret_effects(effects, caster, aim, grid).damage <= 0 || hits_taunter(aim) {
+        return aim;
+    }
+
+    taunters
+        .iter()
+        .filter(|coord| hits_taunter(**coord))
+        .min_by_key(|coord| coord.manhattan_distance(aim))
+        .copied()
+        .unwrap_or(aim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deal_damage_string() {
+        let amount = 4;
+        let effect_string = format!("deal({})", amount);
+        let actual = Effect::from_string(effect_string).unwrap();
+
+        let expected = Effect::DealDamage(amount);
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn test_multiple_commands_in_string() {
+        let expected = Ok(vec![
+            Effect::DealDamage(3),
+            Effect::Block(4),
+            Effect::DefineTarget(TargetShape::Horizontal(1)),
+        ]);
+        let effect_string = "deal(3);block(4)\n;\nhorizontal(1);";
+
+        let actual = get_effects_from_text(effect_string.to_string());
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn test_interpret_single_cell() {
+        let grid = BattleGrid::new();
+        let effects = get_effects_from_text("deal(2); block(1); deal(1);".to_string()).unwrap();
+
+        let actual = interpret_effects(
+            &effects,
+            get_dragon_origin(&grid),
+            Coordinate { x: 2, y: 2 },
+            &grid,
+        );
+        let expected = EffectOutcome {
+            targets: vec![Coordinate { x: 2, y: 2 }],
+            damage: 3,
+            block: 1,
+            forced_move: 0,
+            hazards: Vec::new(),
+            card_changes: Vec::new(),
+            initiative_changes: Vec::new(),
+        };
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn test_interpret_shapes_are_clipped_to_grid() {
+        let grid = BattleGrid::new();
+        let effects =
+            get_effects_from_text("vertical(1); horizontal(1); deal(1);".to_string()).unwrap();
+
+        let actual = interpret_effects(
+            &effects,
+            get_dragon_origin(&grid),
+            Coordinate { x: 0, y: 0 },
+            &grid,
+        );
+
+        assert_eq!(
+            actual.targets,
+            vec![
+                Coordinate { x: 0, y: 0 },
+                Coordinate { x: 0, y: 1 },
+                Coordinate { x: 1, y: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cover_blocks_unless_pierced() {
+        let mut grid = BattleGrid::new();
+        grid.set_status_at_coord(&Coordinate { x: 2, y: 1 }, TileStatus::Blocking);
+        let aim = Coordinate { x: 2, y: 1 };
+
+        let breath = get_effects_from_text("vertical(1); deal(1);".to_string()).unwrap();
+        let actual = interpret_effects(&breath, get_dragon_origin(&grid), aim, &grid);
+        assert_eq!(
+            actual.targets,
+            vec![Coordinate { x: 2, y: 1 }, Coordinate { x: 2, y: 2 }]
+        );
+
+        let piercing =
+            get_effects_from_text("vertical(1); pierce(1); deal(1);".to_string()).unwrap();
+        let actual = interpret_effects(&piercing, get_dragon_origin(&grid), aim, &grid);
+        assert_eq!(actual.targets.len(), 3);
+    }
+
+    #[test]
+    fn test_first_in_line_stops_at_nearest_piece() {
+        let mut grid = BattleGrid::new();
+        grid.set_status_at_coord(&Coordinate { x: 2, y: 2 }, TileStatus::Occupied);
+        grid.set_status_at_coord(&Coordinate { x: 2, y: 0 }, TileStatus::Occupied);
+        let effects = get_effects_from_text("line(5); first(); deal(1);".to_string()).unwrap();
+
+        let actual = interpret_effects(
+            &effects,
+            get_dragon_origin(&grid),
+            Coordinate { x: 2, y: 0 },
+            &grid,
+        );
+
+        assert_eq!(
+            actual.targets,
+            vec![
+                Coordinate { x: 2, y: 4 },
+                Coordinate { x: 2, y: 3 },
+                Coordinate { x: 2, y: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_taunt_redirects_attacks_that_miss() {
+        let mut grid = BattleGrid::new();
+        let taunter = Coordinate { x: 3, y: 0 };
+        grid.set_status_at_coord(&taunter, TileStatus::Blocking);
+        let origin = get_dragon_origin(&grid);
+
+        let strike = get_effects_from_text("deal(2);".to_string()).unwrap();
+        let aim = Coordinate { x: 0, y: 0 };
+        assert_eq!(
+            get_taunted_aim(&strike, origin, aim, &[taunter], &grid),
+            taunter
+        );
+        assert_eq!(get_taunted_aim(&strike, origin, aim, &[], &grid), aim);
+
+        let blast = get_effects_from_text("radius(1); deal(1);".to_string()).unwrap();
+        let near = Coordinate { x: 3, y: 1 };
+        assert_eq!(
+            get_taunted_aim(&blast, origin, near, &[taunter], &grid),
+            near
+        );
+
+        let guard = get_effects_from_text("block(3);".to_string()).unwrap();
+        assert_eq!(get_taunted_aim(&guard, origin, aim, &[taunter], &grid), aim);
+    }
+
+    #[test]
+    fn test_initiative_change_strings() {
+        assert_eq!(
+            get_effects_from_text("delay(2); haste_next(1);".to_string()),
+            Ok(vec![
+                Effect::ChangeInitiative(-2, InitiativeTiming::CurrentRound),
+                Effect::ChangeInitiative(1, InitiativeTiming::NextRound),
+            ])
+        );
+        assert_eq!(
+            Effect::from_string("haste(3)".to_string()),
+            Ok(Effect::ChangeInitiative(3, InitiativeTiming::CurrentRound))
+        );
+        assert_eq!(
+            Effect::from_string("delay_next(1)".to_string()),
+            Ok(Effect::ChangeInitiative(-1, InitiativeTiming::NextRound))
+        );
+    }
+
+    #[test]
+    fn test_push_and_pull_add_up() {
+        let grid = BattleGrid::new();
+        let effects = get_effects_from_text("push(3); pull(1);".to_string()).unwrap();
+
+        let actual = interpret_effects(
+            &effects,
+            get_dragon_origin(&grid),
+            Coordinate { x: 1, y: 1 },
+            &grid,
+        );
+
+        assert_eq!(actual.forced_move, 2);
+    }
+
+    #[test]
+    fn test_parse_card_changes() {
+        let actual =
+            get_effects_from_text("draw(2); create(1); add_to_deck(7); scry(3);".to_string());
+
+        let expected = Ok(vec![
+            Effect::ChangeCards(CardChange::Draw(2)),
+            Effect::ChangeCards(CardChange::CreateTemporary(1)),
+            Effect::ChangeCards(CardChange::AddToDeck(7)),
+            Effect::ChangeCards(CardChange::Scry(3)),
+        ]);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn test_parse_target_shapes() {
+        let actual = get_effects_from_text(
+            "single(); cone(2); rows(0, 1); target_bottom_rows(3); chain(2, 3); ice(2);"
+                .to_string(),
+        );
+
+        let expected = Ok(vec![
+            Effect::DefineTarget(TargetShape::Single),
+            Effect::DefineTarget(TargetShape::Cone(2)),
+            Effect::DefineTarget(TargetShape::Rows { first: 0, last: 1 }),
+            Effect::DefineTarget(TargetShape::BottomRows(3)),
+            Effect::DefineTarget(TargetShape::Chain {
+                bounces: 2,
+                range: 3,
+            }),
+            Effect::CreateHazard(HazardKind::Ice, 2),
+        ]);
+
+        assert_eq!(actual, expected)
+    }
+}
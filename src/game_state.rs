@@ -1,5 +1,10 @@
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum GameState {
+    TitleScreen,
     Initialization,
     Combat,
+    Paused,
+    Settings,
+    GameOver { victory: bool },
+    Quit,
 }
@@ -0,0 +1,112 @@
+use crate::prelude::*;
+
+// Effects left lying on grid cells for a few rounds. They trigger when a piece walks or is shoved
+// onto them, and again when a piece ends its turn standing on one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HazardKind {
+    // Burns on the way in and at the end of every turn spent in it.
+    Fire,
+    // Heals whoever ends their turn in it.
+    Healing,
+    // Stops a walk on the first icy cell it reaches.
+    Ice,
+}
+
+impl HazardKind {
+    pub fn overlay_color(&self) -> Color {
+        match self {
+            HazardKind::Fire => Color::new(1.0, 0.4, 0.0, 0.45),
+            HazardKind::Healing => Color::new(0.2, 0.9, 0.3, 0.4),
+            HazardKind::Ice => Color::new(0.6, 0.85, 1.0, 0.5),
+        }
+    }
+
+    pub fn texture_path(&self) -> &'static str {
+        match self {
+            HazardKind::Fire => "tiles/fire.png",
+            HazardKind::Healing => "tiles/healing.png",
+            HazardKind::Ice => "tiles/ice.png",
+        }
+    }
+
+    // Health lost for stepping onto the cell.
+    pub fn enter_damage(&self) -> i32 {
+        match self {
+            HazardKind::Fire => 1,
+            HazardKind::Healing | HazardKind::Ice => 0,
+        }
+    }
+
+    // Health lost for ending a turn on the cell. Negative values heal.
+    pub fn end_of_turn_damage(&self) -> i32 {
+        match self {
+            HazardKind::Fire => 1,
+            HazardKind::Healing => -1,
+            HazardKind::Ice => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hazard {
+    pub coord: Coordinate,
+    pub kind: HazardKind,
+    // Counts down at the end of every round, including the one it was placed in.
+    pub rounds_left: i32,
+}
+
+// The total damage for walking `path`, ignoring its first cell which is where the piece started.
+pub fn get_enter_damage(path: &[Coordinate], grid: &BattleGrid) -> i32 {
+    path.iter()
+        .skip(1)
+        .filter_map(|coord| grid.get_hazard_at(*coord))
+        .map(|hazard| hazard.kind.enter_damage())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_hazards_replace_old_ones() {
+        let mut grid = BattleGrid::new();
+        let coord = Coordinate { x: 1, y: 1 };
+
+        grid.place_hazard(coord, HazardKind::Fire, 2);
+        grid.place_hazard(coord, HazardKind::Ice, 1);
+
+        assert_eq!(grid.hazards.len(), 1);
+        assert_eq!(grid.get_hazard_at(coord).unwrap().kind, HazardKind::Ice);
+    }
+
+    #[test]
+    fn test_hazards_expire() {
+        let mut grid = BattleGrid::new();
+        grid.place_hazard(Coordinate { x: 0, y: 0 }, HazardKind::Fire, 2);
+        grid.place_hazard(Coordinate { x: 1, y: 0 }, HazardKind::Healing, 1);
+
+        grid.tick_hazards();
+        assert_eq!(grid.hazards.len(), 1);
+        assert_eq!(grid.hazards[0].rounds_left, 1);
+
+        grid.tick_hazards();
+        assert!(grid.hazards.is_empty());
+    }
+
+    #[test]
+    fn test_enter_damage_skips_the_start() {
+        let mut grid = BattleGrid::new();
+        grid.place_hazard(Coordinate { x: 0, y: 0 }, HazardKind::Fire, 1);
+        grid.place_hazard(Coordinate { x: 1, y: 0 }, HazardKind::Fire, 1);
+        grid.place_hazard(Coordinate { x: 2, y: 0 }, HazardKind::Ice, 1);
+
+        let path = [
+            Coordinate { x: 0, y: 0 },
+            Coordinate { x: 1, y: 0 },
+            Coordinate { x: 2, y: 0 },
+        ];
+
+        assert_eq!(get_enter_damage(&path, &grid), 1);
+    }
+}
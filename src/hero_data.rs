@@ -0,0 +1,509 @@
+use crate::prelude::*;
+use rusqlite::Connection;
+
+pub struct HeroDB {
+    connection: Connection,
+}
+
+impl HeroDB {
+    pub fn new() -> Self {
+        HeroDB {
+            connection: Connection::open("./assets/heroes.db").unwrap(),
+        }
+    }
+
+    pub fn get_hero_from_id(&mut self, id: i32) -> HeroData {
+        let mut stmt = self
+            .connection
+            .prepare(&format!(
+                "SELECT HeroID, Name, Health, ActionPoints, Movement, InitMod, Color, Sprite, DiagonalMovement, BlocksSight, Rank, Taunt FROM Heroes WHERE HeroID={}",
+                id
+            ))
+            .unwrap();
+
+        let mut hero_iter = stmt
+            .query_map([], |row| {
+                Ok(HeroData {
+                    id: row.get(0).unwrap(),
+                    name: row.get(1).unwrap(),
+                    health: row.get(2).unwrap(),
+                    action_points: row.get(3).unwrap(),
+                    movement: row.get(4).unwrap(),
+                    init_mod: row.get(5).unwrap(),
+                    color: row.get(6).unwrap(),
+                    sprite: row.get(7).unwrap(),
+                    diagonal_movement: row.get(8).unwrap(),
+                    blocks_sight: row.get(9).unwrap(),
+                    rank: HeroRank::from_id(row.get(10).unwrap()),
+                    taunt: row.get(11).unwrap(),
+                    abilities: Vec::new(),
+                    phases: Vec::new(),
+                })
+            })
+            .unwrap();
+
+        let mut hero = hero_iter.nth(0).unwrap().unwrap();
+        hero.abilities = self.get_abilities_for_hero(id);
+        hero.phases = self.get_phases_for_hero(id);
+
+        hero
+    }
+
+    // Every hero up to `max_rank` the party generator can pick, with what each costs out of the
+    // raid's budget.
+    pub fn get_hero_pool(&mut self, max_rank: HeroRank) -> Vec<(i32, i32)> {
+        let mut stmt = self
+            .connection
+            .prepare(&format!(
+                "SELECT HeroID, Cost FROM Heroes WHERE Rank<={} ORDER BY HeroID",
+                max_rank as i32
+            ))
+            .unwrap();
+
+        let pool_iter = stmt
+            .query_map([], |row| Ok((row.get(0).unwrap(), row.get(1).unwrap())))
+            .unwrap();
+
+        pool_iter
+            .collect::<Result<Vec<(i32, i32)>, rusqlite::Error>>()
+            .unwrap()
+    }
+
+    pub fn get_hero_ids_of_rank(&mut self, rank: HeroRank) -> Vec<i32> {
+        let mut stmt = self
+            .connection
+            .prepare(&format!(
+                "SELECT HeroID FROM Heroes WHERE Rank={} ORDER BY HeroID",
+                rank as i32
+            ))
+            .unwrap();
+
+        let id_iter = stmt.query_map([], |row| row.get(0)).unwrap();
+
+        id_iter
+            .collect::<Result<Vec<i32>, rusqlite::Error>>()
+            .unwrap()
+    }
+
+    fn get_abilities_for_hero(&self, hero_id: i32) -> Vec<AbilityData> {
+        let mut stmt = self
+            .connection
+            .prepare(&format!(
+                "SELECT Name, Effects, Weight, Phase FROM HeroAbilities WHERE HeroID={} ORDER BY AbilityID",
+                hero_id
+            ))
+            .unwrap();
+
+        let ability_iter = stmt
+            .query_map([], |row| {
+                Ok(AbilityData {
+                    name: row.get(0).unwrap(),
+                    effects: row.get(1).unwrap(),
+                    weight: row.get(2).unwrap(),
+                    phase: row.get(3).unwrap(),
+                })
+            })
+            .unwrap();
+
+        ability_iter
+            .collect::<Result<Vec<AbilityData>, rusqlite::Error>>()
+            .unwrap()
+    }
+
+    fn get_phases_for_hero(&self, hero_id: i32) -> Vec<PhaseData> {
+        let mut stmt = self
+            .connection
+            .prepare(&format!(
+                "SELECT Phase, HealthPercent, Reinforcements FROM HeroPhases WHERE HeroID={} ORDER BY Phase",
+                hero_id
+            ))
+            .unwrap();
+
+        let phase_iter = stmt
+            .query_map([], |row| {
+                Ok(PhaseData {
+                    phase: row.get(0).unwrap(),
+                    health_percent: row.get(1).unwrap(),
+                    reinforcements: row.get(2).unwrap(),
+                })
+            })
+            .unwrap();
+
+        phase_iter
+            .collect::<Result<Vec<PhaseData>, rusqlite::Error>>()
+            .unwrap()
+    }
+}
+
+// Elites only join raids past the first, and champions lead the final one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HeroRank {
+    #[default]
+    Normal,
+    Elite,
+    Champion,
+}
+
+impl HeroRank {
+    fn from_id(id: i32) -> Self {
+        match id {
+            1 => HeroRank::Elite,
+            2 => HeroRank::Champion,
+            _ => HeroRank::Normal,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct HeroData {
+    id: i32,
+    pub name: String,
+    health: i32,
+    action_points: i32,
+    movement: i32,
+    diagonal_movement: bool,
+    // Whether the hero shields the pieces behind it from the dragon.
+    blocks_sight: bool,
+    rank: HeroRank,
+    // Whether the dragon's attacks are drawn to this hero, see `get_taunted_aim`.
+    taunt: bool,
+    init_mod: i32,
+    // Hex RGB, e.g. `FFA100`.
+    color: String,
+    sprite: Option<String>,
+    abilities: Vec<AbilityData>,
+    phases: Vec<PhaseData>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct AbilityData {
+    name: String,
+    effects: String,
+    // How likely the AI is to pick this ability relative to the hero's others.
+    weight: i32,
+    // Only used once the hero reaches this phase. Most heroes only ever have phase 0.
+    phase: i32,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct PhaseData {
+    phase: i32,
+    // The phase starts once the hero drops to this share of its max health.
+    health_percent: i32,
+    // Comma separated hero ids, e.g. `5, 5`.
+    reinforcements: Option<String>,
+}
+
+// A parsed hero phase. Reaching it swaps the hero's abilities and calls in the reinforcements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeroPhase {
+    pub health_below: i32,
+    pub abilities: Vec<Ability>,
+    pub reinforcements: Vec<i32>,
+}
+
+// A parsed hero ability, ready for the AI to use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ability {
+    pub name: String,
+    pub effects: Vec<Effect>,
+    pub weight: i32,
+}
+
+impl HeroData {
+    // Toughens the hero up for the raid's difficulty.
+    pub fn scaled(mut self, difficulty: &Difficulty) -> Self {
+        self.health += difficulty.health_bonus;
+        self.init_mod += difficulty.init_bonus;
+        self
+    }
+
+    pub fn get_init_mod(&self) -> i32 {
+        self.init_mod
+    }
+
+    pub fn get_tile_status(&self) -> TileStatus {
+        match self.blocks_sight {
+            true => TileStatus::Blocking,
+            false => TileStatus::Occupied,
+        }
+    }
+
+    fn get_abilities_for_phase(&self, phase: i32) -> Result<Vec<Ability>, String> {
+        self.abilities
+            .iter()
+            .filter(|ability| ability.phase == phase)
+            .map(|ability| {
+                Ok(Ability {
+                    name: ability.name.to_owned(),
+                    effects: get_effects_from_text(ability.effects.to_owned())?,
+                    weight: ability.weight,
+                })
+            })
+            .collect()
+    }
+
+    fn get_phases(&self) -> Result<Vec<HeroPhase>, String> {
+        self.phases
+            .iter()
+            .map(|phase| {
+                let reinforcements = phase
+                    .reinforcements
+                    .as_deref()
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|id| id.trim())
+                    .filter(|id| !id.is_empty())
+                    .map(|id| {
+                        id.parse::<i32>().map_err(|_| {
+                            format!("Invalid reinforcement {} for hero {}", id, self.name)
+                        })
+                    })
+                    .collect::<Result<Vec<i32>, String>>()?;
+
+                Ok(HeroPhase {
+                    health_below: self.health * phase.health_percent / 100,
+                    abilities: self.get_abilities_for_phase(phase.phase)?,
+                    reinforcements,
+                })
+            })
+            .collect()
+    }
+
+    pub fn spawn_as_entity(
+        &self,
+        commands: &mut CommandBuffer,
+        coord: Coordinate,
+    ) -> Result<Entity, String> {
+        let abilities = self.get_abilities_for_phase(0)?;
+        let phases = self.get_phases()?;
+
+        let color = parse_hex_color(&self.color).ok_or(format!(
+            "Invalid color {} for hero {}",
+            self.color, self.name
+        ))?;
+
+        let entity = commands.push((
+            Enemy,
+            Initiative {
+                init_mod: self.init_mod,
+                priority: 0,
+            },
+            Name {
+                val: self.name.to_owned(),
+            },
+            coord,
+            color,
+            Health::new(self.health),
+            ActionPoints::new(self.action_points),
+            self.get_tile_status(),
+        ));
+
+        commands.add_component(entity, self.rank);
+
+        commands.add_component(
+            entity,
+            Movement {
+                max: self.movement,
+                diagonal: self.diagonal_movement,
+            },
+        );
+        commands.add_component(entity, Abilities { list: abilities });
+
+        if !phases.is_empty() {
+            commands.add_component(
+                entity,
+                Phases {
+                    list: phases,
+                    reached: 0,
+                },
+            );
+        }
+
+        if self.taunt {
+            commands.add_component(entity, Taunt);
+        }
+
+        if let Some(sprite) = &self.sprite {
+            commands.add_component(
+                entity,
+                Sprite {
+                    path: sprite.to_owned(),
+                },
+            );
+        }
+
+        Ok(entity)
+    }
+}
+
+fn parse_hex_color(val: &str) -> Option<Color> {
+    let rgb = u32::from_str_radix(val.trim_start_matches('#'), 16).ok()?;
+
+    Some(Color::from_rgba(
+        (rgb >> 16) as u8,
+        (rgb >> 8) as u8,
+        rgb as u8,
+        255,
+    ))
+}
+
+// Picks one of the abilities at random, favouring the higher weighted ones. Aggressive heroes
+// sometimes skip the roll and go straight for whatever hits hardest.
+pub fn choose_ability<'a>(
+    abilities: &'a [Ability],
+    aggression: f32,
+    rng: &mut StdRng,
+) -> Option<&'a Ability> {
+    if rng.gen::<f32>() < aggression {
+        return abilities
+            .iter()
+            .filter(|ability| ability.weight > 0)
+            .max_by_key(|ability| get_ability_damage(ability));
+    }
+
+    abilities
+        .choose_weighted(rng, |ability| ability.weight.max(0))
+        .ok()
+}
+
+// How many of the phases the hero's health has dropped far enough to reach. They're ordered by
+// falling health, so a big hit can skip straight through several.
+pub fn get_reached_phases(phases: &[HeroPhase], health: i32) -> usize {
+    phases
+        .iter()
+        .take_while(|phase| health <= phase.health_below)
+        .count()
+}
+
+fn get_ability_damage(ability: &Ability) -> i32 {
+    ability
+        .effects
+        .iter()
+        .map(|effect| match effect {
+            Effect::DealDamage(amount) => *amount,
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hero_by_id() {
+        let mut db = HeroDB::new();
+
+        let actual = db.get_hero_from_id(1);
+
+        assert_eq!(actual.name, "Fighter");
+        assert_eq!(actual.health, 8);
+        assert_eq!(actual.movement, 2);
+        assert!(actual.blocks_sight);
+        assert_eq!(
+            actual.abilities[0],
+            AbilityData {
+                name: "Cleave".to_string(),
+                effects: "horizontal(1);\ndeal(1);".to_string(),
+                weight: 3,
+                phase: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_champion_phases() {
+        let champion = HeroDB::new().get_hero_from_id(8);
+        let phases = champion.get_phases().unwrap();
+
+        assert_eq!(champion.rank, HeroRank::Champion);
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0].reinforcements, vec![5, 5]);
+        assert!(phases[0].health_below > phases[1].health_below);
+        assert!(phases.iter().all(|phase| !phase.abilities.is_empty()));
+
+        assert_eq!(get_reached_phases(&phases, champion.health), 0);
+        assert_eq!(get_reached_phases(&phases, phases[0].health_below), 1);
+        assert_eq!(get_reached_phases(&phases, 0), 2);
+    }
+
+    #[test]
+    fn test_hero_pool_respects_rank() {
+        let mut db = HeroDB::new();
+        let normal = db.get_hero_pool(HeroRank::Normal);
+        let elite = db.get_hero_pool(HeroRank::Elite);
+
+        assert!(!normal.iter().any(|(id, _)| *id == 6));
+        assert!(elite.iter().any(|(id, _)| *id == 6));
+        assert_eq!(db.get_hero_ids_of_rank(HeroRank::Champion), vec![8]);
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(
+            parse_hex_color("FF0000"),
+            Some(Color::from_rgba(255, 0, 0, 255))
+        );
+        assert_eq!(parse_hex_color("nope"), None);
+    }
+
+    #[test]
+    fn test_choose_ability_skips_zero_weights() {
+        let abilities = vec![
+            Ability {
+                name: "Never".to_string(),
+                effects: vec![],
+                weight: 0,
+            },
+            Ability {
+                name: "Always".to_string(),
+                effects: vec![],
+                weight: 1,
+            },
+        ];
+
+        let mut rng = StdRng::seed_from_u64(0);
+        (0..20).for_each(|_| {
+            assert_eq!(
+                choose_ability(&abilities, 0.0, &mut rng).unwrap().name,
+                "Always"
+            )
+        });
+        assert_eq!(choose_ability(&[], 0.0, &mut rng), None);
+    }
+
+    #[test]
+    fn test_aggressive_heroes_hit_hardest() {
+        let abilities = vec![
+            Ability {
+                name: "Heal".to_string(),
+                effects: vec![Effect::Block(2)],
+                weight: 100,
+            },
+            Ability {
+                name: "Smash".to_string(),
+                effects: vec![Effect::DealDamage(2), Effect::DealDamage(1)],
+                weight: 1,
+            },
+        ];
+
+        let mut rng = StdRng::seed_from_u64(0);
+        (0..20).for_each(|_| {
+            assert_eq!(
+                choose_ability(&abilities, 1.0, &mut rng).unwrap().name,
+                "Smash"
+            )
+        });
+    }
+
+    #[test]
+    fn test_scaled_hero() {
+        let hero = HeroDB::new()
+            .get_hero_from_id(3)
+            .scaled(&Difficulty::for_raid(4, 0));
+
+        assert_eq!(hero.health, 8);
+        assert_eq!(hero.init_mod, 3);
+    }
+}
@@ -0,0 +1,370 @@
+use crate::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InputAction {
+    Confirm,
+    Cancel,
+    EndTurn,
+    Undo,
+    NextCard,
+    PrevCard,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    Pause,
+    Step,
+    FastForward,
+    SpeedInstant,
+    SpeedNormal,
+    SpeedSlow,
+    VolumeUp,
+    VolumeDown,
+    Mute,
+}
+
+impl InputAction {
+    pub fn all() -> [InputAction; 19] {
+        [
+            InputAction::Confirm,
+            InputAction::Cancel,
+            InputAction::EndTurn,
+            InputAction::Undo,
+            InputAction::NextCard,
+            InputAction::PrevCard,
+            InputAction::CursorUp,
+            InputAction::CursorDown,
+            InputAction::CursorLeft,
+            InputAction::CursorRight,
+            InputAction::Pause,
+            InputAction::Step,
+            InputAction::FastForward,
+            InputAction::SpeedInstant,
+            InputAction::SpeedNormal,
+            InputAction::SpeedSlow,
+            InputAction::VolumeUp,
+            InputAction::VolumeDown,
+            InputAction::Mute,
+        ]
+    }
+
+    fn config_name(&self) -> &'static str {
+        match self {
+            InputAction::Confirm => "confirm",
+            InputAction::Cancel => "cancel",
+            InputAction::EndTurn => "end_turn",
+            InputAction::Undo => "undo",
+            InputAction::NextCard => "next_card",
+            InputAction::PrevCard => "prev_card",
+            InputAction::CursorUp => "cursor_up",
+            InputAction::CursorDown => "cursor_down",
+            InputAction::CursorLeft => "cursor_left",
+            InputAction::CursorRight => "cursor_right",
+            InputAction::Pause => "pause",
+            InputAction::Step => "step",
+            InputAction::FastForward => "fast_forward",
+            InputAction::SpeedInstant => "speed_instant",
+            InputAction::SpeedNormal => "speed_normal",
+            InputAction::SpeedSlow => "speed_slow",
+            InputAction::VolumeUp => "volume_up",
+            InputAction::VolumeDown => "volume_down",
+            InputAction::Mute => "mute",
+        }
+    }
+
+    fn from_config_name(name: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|action| action.config_name() == name)
+    }
+}
+
+// Maps every action to the keys that trigger it. Loaded from a plain text config where each
+// line reads `action = Key, OtherKey`; anything missing from the file keeps its default.
+pub struct InputBindings {
+    bindings: Vec<(InputAction, Vec<KeyCode>)>,
+}
+
+impl InputBindings {
+    pub fn new() -> Self {
+        let bindings = InputAction::all()
+            .into_iter()
+            .map(|action| {
+                let keys = match action {
+                    InputAction::Confirm => vec![KeyCode::Enter],
+                    InputAction::Cancel => vec![KeyCode::Escape],
+                    InputAction::EndTurn => vec![KeyCode::Space],
+                    InputAction::Undo => vec![KeyCode::Backspace, KeyCode::Z],
+                    InputAction::NextCard => vec![KeyCode::E, KeyCode::Tab],
+                    InputAction::PrevCard => vec![KeyCode::Q],
+                    InputAction::CursorUp => vec![KeyCode::Up, KeyCode::W],
+                    InputAction::CursorDown => vec![KeyCode::Down, KeyCode::S],
+                    InputAction::CursorLeft => vec![KeyCode::Left, KeyCode::A],
+                    InputAction::CursorRight => vec![KeyCode::Right, KeyCode::D],
+                    InputAction::Pause => vec![KeyCode::P],
+                    InputAction::Step => vec![KeyCode::N],
+                    InputAction::FastForward => vec![KeyCode::F],
+                    InputAction::SpeedInstant => vec![KeyCode::Key1],
+                    InputAction::SpeedNormal => vec![KeyCode::Key2],
+                    InputAction::SpeedSlow => vec![KeyCode::Key3],
+                    InputAction::VolumeUp => vec![KeyCode::Equal],
+                    InputAction::VolumeDown => vec![KeyCode::Minus],
+                    InputAction::Mute => vec![KeyCode::M],
+                };
+
+                (action, keys)
+            })
+            .collect::<Vec<(InputAction, Vec<KeyCode>)>>();
+
+        Self { bindings }
+    }
+
+    pub fn load(path: &str) -> Self {
+        let mut bindings = Self::new();
+
+        match std::fs::read_to_string(path) {
+            Ok(text) => match parse_bindings(&text) {
+                Ok(overrides) => overrides
+                    .into_iter()
+                    .for_each(|(action, keys)| bindings.rebind(action, keys)),
+
+                Err(err) => eprintln!("Ignoring key bindings in {path}: {err}"),
+            },
+
+            Err(_) => eprintln!("No key bindings found at {path}, using defaults."),
+        }
+
+        bindings
+    }
+
+    pub fn rebind(&mut self, action: InputAction, keys: Vec<KeyCode>) {
+        if let Some(existing) = self
+            .bindings
+            .iter_mut()
+            .find(|(bound_action, _)| *bound_action == action)
+        {
+            existing.1 = keys;
+        }
+    }
+
+    pub fn get_keys(&self, action: InputAction) -> &[KeyCode] {
+        self.bindings
+            .iter()
+            .find(|(bound_action, _)| *bound_action == action)
+            .map(|(_, keys)| keys.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn is_pressed(&self, action: InputAction) -> bool {
+        self.get_keys(action).iter().any(|key| is_key_pressed(*key))
+    }
+
+    pub fn is_down(&self, action: InputAction) -> bool {
+        self.get_keys(action).iter().any(|key| is_key_down(*key))
+    }
+}
+
+fn parse_bindings(text: &str) -> Result<Vec<(InputAction, Vec<KeyCode>)>, String> {
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, keys) = line
+                .split_once('=')
+                .ok_or(format!("Expected `action = Key` but got: {line}"))?;
+
+            let action = InputAction::from_config_name(name.trim())
+                .ok_or(format!("Unknown action: {}", name.trim()))?;
+
+            let keys = keys
+                .split(',')
+                .map(|key| key.trim())
+                .filter(|key| !key.is_empty())
+                .map(|key| parse_key(key).ok_or(format!("Unknown key: {key}")))
+                .collect::<Result<Vec<KeyCode>, String>>()?;
+
+            Ok((action, keys))
+        })
+        .collect()
+}
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let key = match name {
+        "Space" => KeyCode::Space,
+        "Enter" => KeyCode::Enter,
+        "Escape" => KeyCode::Escape,
+        "Backspace" => KeyCode::Backspace,
+        "Tab" => KeyCode::Tab,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "LeftShift" => KeyCode::LeftShift,
+        "LeftControl" => KeyCode::LeftControl,
+        "Equal" => KeyCode::Equal,
+        "Minus" => KeyCode::Minus,
+        "Key0" => KeyCode::Key0,
+        "Key1" => KeyCode::Key1,
+        "Key2" => KeyCode::Key2,
+        "Key3" => KeyCode::Key3,
+        "Key4" => KeyCode::Key4,
+        "Key5" => KeyCode::Key5,
+        "Key6" => KeyCode::Key6,
+        "Key7" => KeyCode::Key7,
+        "Key8" => KeyCode::Key8,
+        "Key9" => KeyCode::Key9,
+        "A" => KeyCode::A,
+        "B" => KeyCode::B,
+        "C" => KeyCode::C,
+        "D" => KeyCode::D,
+        "E" => KeyCode::E,
+        "F" => KeyCode::F,
+        "G" => KeyCode::G,
+        "H" => KeyCode::H,
+        "I" => KeyCode::I,
+        "J" => KeyCode::J,
+        "K" => KeyCode::K,
+        "L" => KeyCode::L,
+        "M" => KeyCode::M,
+        "N" => KeyCode::N,
+        "O" => KeyCode::O,
+        "P" => KeyCode::P,
+        "Q" => KeyCode::Q,
+        "R" => KeyCode::R,
+        "S" => KeyCode::S,
+        "T" => KeyCode::T,
+        "U" => KeyCode::U,
+        "V" => KeyCode::V,
+        "W" => KeyCode::W,
+        "X" => KeyCode::X,
+        "Y" => KeyCode::Y,
+        "Z" => KeyCode::Z,
+        _ => return None,
+    };
+
+    Some(key)
+}
+
+// Keyboard and mouse selection state for the player's declare turn.
+pub struct PlayerCursor {
+    pub selected_card: Option<usize>,
+    pub grid_cursor: Coordinate,
+    // Once a card is chosen, confirming picks the cell it's aimed at.
+    pub targeting: bool,
+    // Where the mouse grabbed the selected card, while it's being dragged.
+    pub drag_origin: Option<Vec2>,
+}
+
+impl PlayerCursor {
+    pub fn new() -> Self {
+        Self {
+            selected_card: None,
+            grid_cursor: Coordinate { x: 0, y: 0 },
+            targeting: false,
+            drag_origin: None,
+        }
+    }
+
+    // Steps through the hand, wrapping at either end.
+    pub fn cycle_card(&mut self, offset: i32, hand_size: usize) {
+        if hand_size == 0 {
+            self.selected_card = None;
+            return;
+        }
+
+        let current = match self.selected_card {
+            Some(idx) => idx.min(hand_size - 1) as i32,
+            None if offset >= 0 => -1,
+            None => 0,
+        };
+
+        self.selected_card = Some((current + offset).rem_euclid(hand_size as i32) as usize);
+    }
+
+    pub fn move_grid_cursor(&mut self, dir: Direction, grid: &BattleGrid) {
+        let moved = self.grid_cursor + Coordinate::from(dir);
+        if grid.is_cell_in_bounds(moved) {
+            self.grid_cursor = moved;
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.drag_origin = None;
+        match self.targeting {
+            true => self.targeting = false,
+            false => self.selected_card = None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_action_has_a_default_binding() {
+        let bindings = InputBindings::new();
+
+        InputAction::all()
+            .iter()
+            .for_each(|action| assert!(!bindings.get_keys(*action).is_empty(), "{action:?}"));
+    }
+
+    #[test]
+    fn test_parse_bindings_overrides() {
+        let text = "# Lefty layout\nconfirm = Space, E\n\nundo=Backspace\n";
+        let parsed = parse_bindings(text).unwrap();
+
+        assert_eq!(
+            parsed,
+            vec![
+                (InputAction::Confirm, vec![KeyCode::Space, KeyCode::E]),
+                (InputAction::Undo, vec![KeyCode::Backspace]),
+            ]
+        );
+
+        let mut bindings = InputBindings::new();
+        parsed
+            .into_iter()
+            .for_each(|(action, keys)| bindings.rebind(action, keys));
+        assert_eq!(
+            bindings.get_keys(InputAction::Confirm),
+            &[KeyCode::Space, KeyCode::E]
+        );
+        assert_eq!(bindings.get_keys(InputAction::Cancel), &[KeyCode::Escape]);
+    }
+
+    #[test]
+    fn test_parse_bindings_rejects_unknown_names() {
+        assert!(parse_bindings("jump = Space").is_err());
+        assert!(parse_bindings("confirm = Hyperspace").is_err());
+        assert!(parse_bindings("confirm Space").is_err());
+    }
+
+    #[test]
+    fn test_card_cycling_wraps() {
+        let mut cursor = PlayerCursor::new();
+
+        cursor.cycle_card(1, 3);
+        assert_eq!(cursor.selected_card, Some(0));
+        cursor.cycle_card(-1, 3);
+        assert_eq!(cursor.selected_card, Some(2));
+        cursor.cycle_card(1, 3);
+        assert_eq!(cursor.selected_card, Some(0));
+
+        cursor.cycle_card(1, 0);
+        assert_eq!(cursor.selected_card, None);
+    }
+
+    #[test]
+    fn test_grid_cursor_stays_in_bounds() {
+        let grid = BattleGrid::new();
+        let mut cursor = PlayerCursor::new();
+
+        cursor.move_grid_cursor(Direction::Up, &grid);
+        assert_eq!(cursor.grid_cursor, Coordinate { x: 0, y: 0 });
+
+        cursor.move_grid_cursor(Direction::Right, &grid);
+        assert_eq!(cursor.grid_cursor, Coordinate { x: 1, y: 0 });
+    }
+}
@@ -0,0 +1,451 @@
+use crate::prelude::*;
+use std::ops::Range;
+
+// Everything is designed against this window size and scaled from it.
+const REFERENCE_SCREEN: (f32, f32) = (1280.0, 720.0);
+
+const TRACKER_HEADER_HEIGHT: f32 = 40.0;
+const TRACKER_MAX_ROW_HEIGHT: f32 = 56.0;
+const TRACKER_MIN_ROW_HEIGHT: f32 = 24.0;
+
+// How far the outermost cards of a full hand tilt away from the center, in radians.
+const HAND_MAX_FAN_ROTATION: f32 = 0.25;
+const HAND_HOVER_SCALE: f32 = 1.3;
+// Cards never sit further apart than this fraction of their width, so small hands stay together.
+const HAND_MAX_SPACING: f32 = 0.9;
+
+// Share of each grid cell's pitch taken up by the border lines.
+const GRID_LINE_FRACTION: f32 = 0.04;
+
+// Screen-space positions of every panel for the current window size. Anchors move
+// proportionally with the window, while sizes scale uniformly so nothing gets stretched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub screen: Vec2,
+    pub scale: f32,
+    pub grid_area: Rect,
+    pub hand_zone_y: f32,
+    pub hand_zone_start: f32,
+    pub hand_zone_end: f32,
+    pub card_size: Vec2,
+    pub card_arc_down: f32,
+    pub tracker: Rect,
+    pub end_turn_button: Rect,
+    pub undo_button: Rect,
+    pub status_pos: Vec2,
+}
+
+impl Layout {
+    pub fn new(screen: Vec2) -> Self {
+        let scale = (screen.x / REFERENCE_SCREEN.0).min(screen.y / REFERENCE_SCREEN.1);
+        let anchor = |x: f32, y: f32| {
+            Vec2::new(
+                screen.x * x / REFERENCE_SCREEN.0,
+                screen.y * y / REFERENCE_SCREEN.1,
+            )
+        };
+
+        let grid_origin = anchor(100.0, 100.0);
+        let hand_start = anchor(700.0, 600.0);
+        let tracker_origin = anchor(1000.0, 64.0);
+        let end_turn_origin = anchor(700.0, 400.0);
+        let undo_origin = anchor(850.0, 400.0);
+
+        Self {
+            screen,
+            scale,
+            grid_area: Rect::new(
+                grid_origin.x,
+                grid_origin.y,
+                screen.x * 0.45,
+                screen.y * 500.0 / REFERENCE_SCREEN.1,
+            ),
+            hand_zone_y: hand_start.y,
+            hand_zone_start: hand_start.x,
+            hand_zone_end: anchor(1200.0, 0.0).x,
+            card_size: Vec2::new(140.0, 200.0) * scale,
+            card_arc_down: 64.0 * scale,
+            tracker: Rect::new(
+                tracker_origin.x,
+                tracker_origin.y,
+                240.0 * scale,
+                500.0 * scale,
+            ),
+            end_turn_button: Rect::new(
+                end_turn_origin.x,
+                end_turn_origin.y,
+                140.0 * scale,
+                44.0 * scale,
+            ),
+            undo_button: Rect::new(undo_origin.x, undo_origin.y, 100.0 * scale, 44.0 * scale),
+            status_pos: anchor(100.0, 40.0),
+        }
+    }
+
+    // Sizes the grid's cells to the largest that fit in the grid area, so screen positions
+    // map back onto the right cells after a resize.
+    pub fn fit_grid(&self, grid: &mut BattleGrid) {
+        let pitch =
+            (self.grid_area.w / grid.width as f32).min(self.grid_area.h / grid.height as f32);
+
+        grid.screen_pos = self.grid_area.point();
+        grid.line_width = pitch * GRID_LINE_FRACTION;
+        grid.grid_size = pitch - grid.line_width;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CardPlacement {
+    pub center: Vec2,
+    // Clockwise, in radians.
+    pub rotation: f32,
+    pub scale: f32,
+}
+
+impl CardPlacement {
+    pub fn contains(&self, point: Vec2, card_size: Vec2) -> bool {
+        let offset = point - self.center;
+        let (sin, cos) = (-self.rotation).sin_cos();
+        let local = Vec2::new(
+            offset.x * cos - offset.y * sin,
+            offset.x * sin + offset.y * cos,
+        );
+        let half_size = card_size * self.scale * 0.5;
+
+        local.x.abs() <= half_size.x && local.y.abs() <= half_size.y
+    }
+}
+
+// Fans the hand out along an arc. Cards overlap more as the hand grows so it always fits the
+// hand zone, and the hovered card is lifted upright and enlarged so it can be read.
+pub fn layout_hand(
+    layout: &Layout,
+    hand_size: usize,
+    hovered: Option<usize>,
+) -> Vec<CardPlacement> {
+    let zone_width = layout.hand_zone_end - layout.hand_zone_start;
+    let spacing = match hand_size {
+        0 | 1 => 0.0,
+        _ => (zone_width - layout.card_size.x).max(0.0) / (hand_size - 1) as f32,
+    }
+    .min(layout.card_size.x * HAND_MAX_SPACING);
+
+    let zone_center = layout.hand_zone_start + zone_width * 0.5;
+    let center_idx = hand_size.saturating_sub(1) as f32 / 2.0;
+
+    (0..hand_size)
+        .map(|idx| {
+            // -1.0 for the leftmost card, 1.0 for the rightmost.
+            let spread = match center_idx > 0.0 {
+                true => (idx as f32 - center_idx) / center_idx,
+                false => 0.0,
+            };
+
+            let center = Vec2::new(
+                zone_center + (idx as f32 - center_idx) * spacing,
+                layout.hand_zone_y + layout.card_arc_down * spread.powi(2),
+            );
+
+            match hovered == Some(idx) {
+                true => CardPlacement {
+                    center: Vec2::new(
+                        center.x,
+                        layout.hand_zone_y - layout.card_size.y * (HAND_HOVER_SCALE - 1.0),
+                    ),
+                    rotation: 0.0,
+                    scale: HAND_HOVER_SCALE,
+                },
+
+                false => CardPlacement {
+                    center,
+                    rotation: spread * HAND_MAX_FAN_ROTATION,
+                    scale: 1.0,
+                },
+            }
+        })
+        .collect()
+}
+
+// The card under `point`, preferring whichever is drawn on top.
+pub fn get_card_at_point(
+    placements: &[CardPlacement],
+    card_size: Vec2,
+    point: Vec2,
+) -> Option<usize> {
+    placements
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, placement)| placement.contains(point, card_size))
+        .map(|(idx, _)| idx)
+}
+
+pub struct TrackerLayout {
+    pub header: Rect,
+    pub rows: Vec<Rect>,
+    // Which combatants the rows belong to, by index into `TurnTracker::combatants`.
+    pub visible: Range<usize>,
+}
+
+// Rows shrink to fit as combatants are added. Once they can't shrink any further the bar
+// scrolls to keep the acting combatant in view.
+pub fn layout_turn_tracker(
+    area: Rect,
+    scale: f32,
+    combatant_count: usize,
+    current: usize,
+) -> TrackerLayout {
+    let header_height = TRACKER_HEADER_HEIGHT * scale;
+    let min_row_height = TRACKER_MIN_ROW_HEIGHT * scale;
+    let max_row_height = TRACKER_MAX_ROW_HEIGHT * scale;
+
+    let header = Rect::new(area.x, area.y, area.w, header_height);
+    let available = (area.h - header_height).max(min_row_height);
+
+    let max_rows = ((available / min_row_height).floor() as usize).max(1);
+    let shown = combatant_count.min(max_rows);
+    let row_height = match shown {
+        0 => max_row_height,
+        _ => (available / shown as f32).clamp(min_row_height, max_row_height),
+    };
+
+    let first = current
+        .saturating_sub(shown / 2)
+        .min(combatant_count - shown);
+    let visible = first..first + shown;
+
+    let rows = (0..shown)
+        .map(|idx| {
+            Rect::new(
+                area.x,
+                header.bottom() + idx as f32 * row_height,
+                area.w,
+                row_height,
+            )
+        })
+        .collect::<Vec<Rect>>();
+
+    TrackerLayout {
+        header,
+        rows,
+        visible,
+    }
+}
+
+// Menu entries stacked down the middle of the screen, leaving the top third for the title.
+pub fn layout_menu(layout: &Layout, count: usize) -> Vec<Rect> {
+    let size = Vec2::new(360.0, 56.0) * layout.scale;
+    let gap = 16.0 * layout.scale;
+    let top = layout.screen.y * 0.35;
+
+    (0..count)
+        .map(|idx| {
+            Rect::new(
+                (layout.screen.x - size.x) * 0.5,
+                top + idx as f32 * (size.y + gap),
+                size.x,
+                size.y,
+            )
+        })
+        .collect()
+}
+
+// Relic icons in a row above the grid, leaving room for a tooltip underneath.
+pub fn layout_relic_bar(layout: &Layout, count: usize) -> Vec<Rect> {
+    let size = 48.0 * layout.scale;
+    let gap = 8.0 * layout.scale;
+    let top = (layout.grid_area.y - size) * 0.3;
+
+    (0..count)
+        .map(|idx| {
+            Rect::new(
+                layout.grid_area.x + idx as f32 * (size + gap),
+                top,
+                size,
+                size,
+            )
+        })
+        .collect()
+}
+
+// Scried cards in a row across the middle of the screen, the top of the deck on the left.
+pub fn layout_scry(layout: &Layout, count: usize) -> Vec<CardPlacement> {
+    let gap = 24.0 * layout.scale;
+    let width = count as f32 * (layout.card_size.x + gap) - gap;
+    let left = (layout.screen.x - width + layout.card_size.x) * 0.5;
+
+    (0..count)
+        .map(|idx| CardPlacement {
+            center: Vec2::new(
+                left + idx as f32 * (layout.card_size.x + gap),
+                layout.screen.y * 0.45,
+            ),
+            rotation: 0.0,
+            scale: 1.0,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_party_uses_full_rows() {
+        let layout = layout_turn_tracker(Rect::new(0.0, 0.0, 200.0, 400.0), 1.0, 3, 0);
+
+        assert_eq!(layout.visible, 0..3);
+        assert_eq!(layout.rows.len(), 3);
+        assert_eq!(layout.rows[0].h, TRACKER_MAX_ROW_HEIGHT);
+        assert_eq!(layout.rows[1].y, layout.rows[0].bottom());
+    }
+
+    #[test]
+    fn test_rows_shrink_to_fit() {
+        let max_height = 300.0;
+        let layout = layout_turn_tracker(Rect::new(0.0, 0.0, 200.0, max_height), 1.0, 8, 0);
+
+        assert_eq!(layout.rows.len(), 8);
+        assert!(layout.rows.last().unwrap().bottom() <= max_height + 0.01);
+    }
+
+    #[test]
+    fn test_huge_roster_scrolls_to_current() {
+        let layout = layout_turn_tracker(Rect::new(0.0, 0.0, 200.0, 200.0), 1.0, 40, 30);
+
+        assert!(layout.rows.len() < 40);
+        assert_eq!(layout.rows.len(), layout.visible.len());
+        assert!(layout.visible.contains(&30));
+        assert!(layout.visible.end <= 40);
+    }
+
+    #[test]
+    fn test_no_combatants() {
+        let layout = layout_turn_tracker(Rect::new(0.0, 0.0, 200.0, 200.0), 1.0, 0, 0);
+
+        assert!(layout.rows.is_empty());
+        assert_eq!(layout.visible, 0..0);
+    }
+
+    #[test]
+    fn test_reference_screen_matches_default_grid() {
+        let layout = Layout::new(Vec2::new(1280.0, 720.0));
+        let mut grid = BattleGrid::new();
+        let default_grid = BattleGrid::new();
+        layout.fit_grid(&mut grid);
+
+        assert_eq!(grid.screen_pos, default_grid.screen_pos);
+        assert!((grid.grid_size - default_grid.grid_size).abs() < 0.01);
+        assert!((grid.line_width - default_grid.line_width).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_resized_grid_fits_and_maps_back_to_cells() {
+        [
+            Vec2::new(1920.0, 1080.0),
+            Vec2::new(800.0, 600.0),
+            Vec2::new(1280.0, 1400.0),
+        ]
+        .iter()
+        .for_each(|screen| {
+            let layout = Layout::new(*screen);
+            let mut grid = BattleGrid::new();
+            layout.fit_grid(&mut grid);
+
+            let bottom_right = grid.get_cell_tl(grid.width, grid.height);
+            assert!(bottom_right.x <= layout.grid_area.right() + 0.01);
+            assert!(bottom_right.y <= layout.grid_area.bottom() + 0.01);
+
+            let center = grid.get_cell_center(2, 3);
+            assert_eq!(
+                grid.get_cell_at_screen_pos(center),
+                Some(Coordinate { x: 2, y: 3 })
+            );
+        });
+    }
+
+    #[test]
+    fn test_hand_of_zero_and_one() {
+        let layout = Layout::new(Vec2::new(1280.0, 720.0));
+
+        assert!(layout_hand(&layout, 0, None).is_empty());
+
+        let single = layout_hand(&layout, 1, None);
+        assert_eq!(single.len(), 1);
+        assert!(single[0].center.x.is_finite() && single[0].center.y.is_finite());
+        assert_eq!(single[0].rotation, 0.0);
+        assert_eq!(single[0].center.y, layout.hand_zone_y);
+    }
+
+    #[test]
+    fn test_hand_fans_symmetrically() {
+        let layout = Layout::new(Vec2::new(1280.0, 720.0));
+        let hand = layout_hand(&layout, 5, None);
+
+        assert!(hand[0].rotation < 0.0);
+        assert_eq!(hand[2].rotation, 0.0);
+        assert!((hand[0].rotation + hand[4].rotation).abs() < 0.0001);
+        assert!((hand[0].center.y - hand[4].center.y).abs() < 0.0001);
+        assert!(hand[0].center.y > hand[2].center.y);
+    }
+
+    #[test]
+    fn test_large_hand_stays_in_zone() {
+        let layout = Layout::new(Vec2::new(1280.0, 720.0));
+        let hand = layout_hand(&layout, 40, None);
+
+        let half_width = layout.card_size.x * 0.5;
+        assert!(hand[0].center.x - half_width >= layout.hand_zone_start - 0.01);
+        assert!(hand[39].center.x + half_width <= layout.hand_zone_end + 0.01);
+    }
+
+    #[test]
+    fn test_hovered_card_is_raised_upright_and_on_top() {
+        let layout = Layout::new(Vec2::new(1280.0, 720.0));
+        let hand = layout_hand(&layout, 5, Some(0));
+
+        assert_eq!(hand[0].rotation, 0.0);
+        assert!(hand[0].scale > 1.0);
+        assert!(hand[0].center.y < layout.hand_zone_y);
+        assert!(hand[0].contains(hand[0].center, layout.card_size));
+    }
+
+    #[test]
+    fn test_rotated_hit_testing() {
+        let card_size = Vec2::new(100.0, 200.0);
+        let upright = CardPlacement {
+            center: Vec2::ZERO,
+            rotation: 0.0,
+            scale: 1.0,
+        };
+        let sideways = CardPlacement {
+            rotation: std::f32::consts::FRAC_PI_2,
+            ..upright
+        };
+
+        assert!(upright.contains(Vec2::new(0.0, 90.0), card_size));
+        assert!(!upright.contains(Vec2::new(90.0, 0.0), card_size));
+        assert!(sideways.contains(Vec2::new(90.0, 0.0), card_size));
+        assert!(!sideways.contains(Vec2::new(0.0, 90.0), card_size));
+
+        let placements = [upright, upright];
+        assert_eq!(
+            get_card_at_point(&placements, card_size, Vec2::ZERO),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_menu_entries_are_centered_and_separate() {
+        let layout = Layout::new(Vec2::new(1280.0, 720.0));
+        let entries = layout_menu(&layout, 3);
+
+        assert_eq!(entries.len(), 3);
+        entries.iter().for_each(|entry| {
+            assert_eq!(entry.x + entry.w * 0.5, 640.0);
+        });
+        entries
+            .windows(2)
+            .for_each(|pair| assert!(pair[0].bottom() < pair[1].top()));
+    }
+}
@@ -1,37 +1,80 @@
 use crate::prelude::*;
+mod animation;
+mod assets;
+mod attack_action;
+mod audio;
 mod battle_grid;
 mod card_data;
 mod card_zones;
+mod combat_events;
 mod components;
 mod coordinate;
 mod direction;
+mod effect;
 mod game_state;
+mod hazard;
+mod hero_data;
+mod input;
+mod layout;
+mod menu;
 mod move_action;
+mod party;
+mod path_layout;
+mod phase_timer;
+mod relic_data;
+mod run;
 mod systems;
+mod targeting;
 mod turn_tracker;
 
 mod prelude {
 
+    pub use crate::animation::*;
+    pub use crate::assets::*;
+    pub use crate::attack_action::*;
+    pub use crate::audio::*;
     pub use crate::battle_grid::*;
     pub use crate::card_data::*;
     pub use crate::card_zones::*;
+    pub use crate::combat_events::*;
     pub use crate::components::*;
     pub use crate::coordinate::*;
     pub use crate::direction::*;
+    pub use crate::effect::*;
     pub use crate::game_state::*;
+    pub use crate::hazard::*;
+    pub use crate::hero_data::*;
+    pub use crate::input::*;
+    pub use crate::layout::*;
+    pub use crate::menu::*;
     pub use crate::move_action::*;
+    pub use crate::party::*;
+    pub use crate::path_layout::*;
+    pub use crate::phase_timer::*;
+    pub use crate::relic_data::*;
+    pub use crate::run::*;
     pub use crate::systems::*;
+    pub use crate::targeting::*;
     pub use crate::turn_tracker::*;
     pub use ::rand::prelude::*;
+    pub use ::rand::rngs::StdRng;
     pub use legion::systems::CommandBuffer;
     pub use legion::world::SubWorld;
     pub use legion::*;
     pub use macroquad::prelude::*;
 }
 
+// Shipped defaults, and where the player's changes are saved so the defaults stay untouched.
+const AUDIO_DEFAULTS_PATH: &str = "./assets/audio.cfg";
+const AUDIO_SETTINGS_PATH: &str = "./audio.cfg";
+
 struct State {
     world: World,
     resources: Resources,
+    title_screen_schedule: Schedule,
+    pause_menu_schedule: Schedule,
+    settings_schedule: Schedule,
+    game_over_schedule: Schedule,
     game_initialization_schedule: Schedule,
     start_of_round_schedule: Schedule,
     declare_phase_schedule: Schedule,
@@ -43,23 +86,54 @@ impl State {
         let world = World::default();
         let mut resources = Resources::default();
 
-        resources.insert(GameState::Initialization);
+        resources.insert(GameState::TitleScreen);
+        resources.insert(MenuState::new());
+        resources.insert(Run::new(Run::random_seed()));
         resources.insert(CardDB::new());
-        resources.insert(CardZones::new());
-
-        resources.insert(BattleGrid::new());
-
-        resources.insert(TurnTracker::new());
-        resources.insert(0.0 as f32);
-
-        Self {
+        resources.insert(HeroDB::new());
+        resources.insert(RelicDB::new());
+        resources.insert(Layout::new(Vec2::new(1280., 720.)));
+        resources.insert(PhaseTimer::new(PhaseSpeed::Normal));
+        resources.insert(InputBindings::load("./assets/bindings.cfg"));
+        resources.insert(TextureAssets::new("./assets"));
+
+        let mut state = Self {
             world,
             resources,
+            title_screen_schedule: build_title_screen_schedule(),
+            pause_menu_schedule: build_pause_menu_schedule(),
+            settings_schedule: build_settings_schedule(),
+            game_over_schedule: build_game_over_schedule(),
             game_initialization_schedule: build_game_initialization_schedule(),
             start_of_round_schedule: build_start_of_round_schedule(),
             declare_phase_schedule: build_declare_phase_schedule(),
             resolve_phase_schedule: build_resolve_phase_schedule(),
-        }
+        };
+        state.reset_combat();
+
+        state
+    }
+
+    // Clears out the previous fight so a new one can be set up from the current run.
+    fn reset_combat(&mut self) {
+        self.world.clear();
+
+        let layout = self.resources.get::<Layout>().unwrap().clone();
+        let mut grid = BattleGrid::new();
+        layout.fit_grid(&mut grid);
+        self.resources.insert(grid);
+
+        let run = self.resources.get::<Run>().unwrap().clone();
+        self.resources.insert(run.build_rng());
+        self.resources
+            .insert(Difficulty::for_raid(run.difficulty, run.floor));
+
+        self.resources.insert(CardZones::new());
+        self.resources.insert(Relics::new());
+        self.resources.insert(TurnTracker::new());
+        self.resources.insert(CombatEvents::new());
+        self.resources.insert(Animations::new());
+        self.resources.insert(PlayerCursor::new());
     }
 }
 
@@ -68,6 +142,7 @@ fn window_conf() -> Conf {
         window_title: "Raid Night".to_owned(),
         window_width: 1280,
         window_height: 720,
+        window_resizable: true,
         ..Default::default()
     }
 }
@@ -76,52 +151,58 @@ fn window_conf() -> Conf {
 async fn main() {
     let mut state = State::new();
 
-    state.world.push((
-        Player,
-        Initiative {
-            init_mod: 0,
-            priority: 100,
-        },
-        Name {
-            val: "You".to_string(),
-        },
-    ));
-
-    (0..3).for_each(|num| {
-        let (name, init_mod, (y, x), color) = match num {
-            0 => ("Fighter", -1, (1, 2), ORANGE),
-            1 => ("Cleric", 0, (0, 1), BLUE),
-            2 => ("Wizard", 1, (0, 3), RED),
-            _ => ("Hero", 0, (0, 0), PINK),
-        };
-
-        state.world.push((
-            Enemy,
-            Initiative {
-                init_mod,
-                priority: 0,
-            },
-            Name {
-                val: name.to_string(),
-            },
-            Coordinate { x, y },
-            color,
-            ActionPoints::new(3),
-            TileStatus::Occupied,
-        ));
-    });
+    // Headless runs have no audio device to open.
+    let settings = AudioSettings::load_or_defaults(AUDIO_SETTINGS_PATH, AUDIO_DEFAULTS_PATH);
+    let audio = match std::env::args().any(|arg| arg == "--silent") {
+        true => AudioManager::silent(settings, AUDIO_SETTINGS_PATH),
+        false => AudioManager::load("./assets", settings, AUDIO_SETTINGS_PATH).await,
+    };
+    state.resources.insert(audio);
 
     loop {
         let game_state = state.resources.get::<GameState>().unwrap().clone();
 
         match game_state {
+            GameState::TitleScreen => {
+                state
+                    .title_screen_schedule
+                    .execute(&mut state.world, &mut state.resources);
+            }
+
+            GameState::Paused => {
+                state
+                    .pause_menu_schedule
+                    .execute(&mut state.world, &mut state.resources);
+            }
+
+            GameState::Settings => {
+                state
+                    .settings_schedule
+                    .execute(&mut state.world, &mut state.resources);
+            }
+
+            GameState::GameOver { .. } => {
+                state
+                    .game_over_schedule
+                    .execute(&mut state.world, &mut state.resources);
+            }
+
+            GameState::Quit => break,
+
             GameState::Initialization => {
+                state.reset_combat();
                 state
                     .game_initialization_schedule
                     .execute(&mut state.world, &mut state.resources);
             }
 
             GameState::Combat => {
+                state
+                    .resources
+                    .get_mut::<TurnTracker>()
+                    .unwrap()
+                    .publish_events();
+                state.resources.get_mut::<CombatEvents>().unwrap().publish();
                 let turn_tracker = state.resources.get::<TurnTracker>().unwrap().clone();
 
                 match turn_tracker.turn_state {
@@ -0,0 +1,168 @@
+use crate::prelude::*;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MenuItem {
+    NewRun,
+    Continue,
+    Seed,
+    Difficulty,
+    NextRaid,
+    Settings,
+    Resume,
+    SaveAndQuit,
+    QuitToTitle,
+    Quit,
+    MasterVolume,
+    Mute,
+    Speed,
+    Back,
+}
+
+// The entries shown on each menu screen, top to bottom.
+pub fn get_menu_items(state: GameState, has_save: bool) -> Vec<MenuItem> {
+    match state {
+        GameState::TitleScreen => {
+            let mut items = vec![MenuItem::NewRun];
+            if has_save {
+                items.push(MenuItem::Continue);
+            }
+            items.extend([
+                MenuItem::Seed,
+                MenuItem::Difficulty,
+                MenuItem::Settings,
+                MenuItem::Quit,
+            ]);
+            items
+        }
+
+        GameState::Paused => vec![
+            MenuItem::Resume,
+            MenuItem::Settings,
+            MenuItem::SaveAndQuit,
+            MenuItem::Quit,
+        ],
+
+        GameState::Settings => vec![
+            MenuItem::MasterVolume,
+            MenuItem::Mute,
+            MenuItem::Speed,
+            MenuItem::Back,
+        ],
+
+        // Only a win short of the final raid leaves a saved run to carry on from.
+        GameState::GameOver { victory: true } if has_save => {
+            vec![MenuItem::NextRaid, MenuItem::QuitToTitle, MenuItem::Quit]
+        }
+
+        GameState::GameOver { .. } => {
+            vec![MenuItem::NewRun, MenuItem::QuitToTitle, MenuItem::Quit]
+        }
+
+        GameState::Initialization | GameState::Combat | GameState::Quit => Vec::new(),
+    }
+}
+
+pub fn get_menu_title(state: GameState) -> &'static str {
+    match state {
+        GameState::TitleScreen => "Raid Night",
+        GameState::Paused => "Paused",
+        GameState::Settings => "Settings",
+        GameState::GameOver { victory: true } => "Victory!",
+        GameState::GameOver { victory: false } => "Defeat",
+        GameState::Initialization | GameState::Combat | GameState::Quit => "",
+    }
+}
+
+pub struct MenuState {
+    pub selected: usize,
+    // Where backing out of the settings screen goes, since it opens from several menus.
+    pub settings_return: GameState,
+}
+
+impl MenuState {
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            settings_return: GameState::TitleScreen,
+        }
+    }
+
+    pub fn move_selection(&mut self, offset: i32, item_count: usize) {
+        if item_count == 0 {
+            self.selected = 0;
+            return;
+        }
+
+        let current = self.selected.min(item_count - 1) as i32;
+        self.selected = (current + offset).rem_euclid(item_count as i32) as usize;
+    }
+
+    pub fn open(&mut self, game_state: &mut GameState, next: GameState) {
+        if next == GameState::Settings {
+            self.settings_return = *game_state;
+        }
+
+        *game_state = next;
+        self.selected = 0;
+    }
+}
+
+pub fn adjust_difficulty(difficulty: u32, offset: i32) -> u32 {
+    (difficulty as i32 + offset).clamp(0, MAX_DIFFICULTY as i32) as u32
+}
+
+pub fn cycle_speed(speed: PhaseSpeed, offset: i32) -> PhaseSpeed {
+    let speeds = [PhaseSpeed::Instant, PhaseSpeed::Normal, PhaseSpeed::Slow];
+    let current = speeds.iter().position(|s| *s == speed).unwrap_or(1) as i32;
+
+    speeds[(current + offset).rem_euclid(speeds.len() as i32) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continue_only_with_a_save() {
+        assert!(!get_menu_items(GameState::TitleScreen, false).contains(&MenuItem::Continue));
+        assert_eq!(
+            get_menu_items(GameState::TitleScreen, true)[1],
+            MenuItem::Continue
+        );
+    }
+
+    #[test]
+    fn test_settings_returns_to_opener() {
+        let mut menu = MenuState::new();
+        let mut game_state = GameState::Paused;
+        menu.selected = 2;
+
+        menu.open(&mut game_state, GameState::Settings);
+        assert_eq!(game_state, GameState::Settings);
+        assert_eq!(menu.settings_return, GameState::Paused);
+        assert_eq!(menu.selected, 0);
+
+        menu.move_selection(-1, 4);
+        assert_eq!(menu.selected, 3);
+    }
+
+    #[test]
+    fn test_victory_leads_to_the_next_raid() {
+        let items = get_menu_items(GameState::GameOver { victory: true }, true);
+        assert_eq!(items[0], MenuItem::NextRaid);
+        assert!(!items.contains(&MenuItem::NewRun));
+        assert_eq!(
+            get_menu_items(GameState::GameOver { victory: true }, false)[0],
+            MenuItem::NewRun
+        );
+
+        assert_eq!(adjust_difficulty(0, -1), 0);
+        assert_eq!(adjust_difficulty(MAX_DIFFICULTY, 1), MAX_DIFFICULTY);
+    }
+
+    #[test]
+    fn test_cycle_speed_wraps() {
+        assert_eq!(cycle_speed(PhaseSpeed::Slow, 1), PhaseSpeed::Instant);
+        assert_eq!(cycle_speed(PhaseSpeed::Instant, -1), PhaseSpeed::Slow);
+    }
+}
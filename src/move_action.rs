@@ -6,17 +6,17 @@ pub fn generate_random_walk(
     depth: i32,
     max_depth: i32,
     last_dir: Option<Direction>,
+    diagonal: bool,
+    rng: &mut StdRng,
 ) -> Vec<Direction> {
-    let all_dirs = Direction::all();
-    let random_dir = all_dirs
-        .iter()
-        .filter(|dir| last_dir.is_none() || last_dir.unwrap().reverse() != **dir)
-        .filter(|dir| grid.is_cell_in_bounds(pos + Coordinate::from(**dir)))
-        .choose::<ThreadRng>(&mut thread_rng());
-
-    if random_dir.is_some() {
-        let chosen_dir = *random_dir.unwrap();
+    let random_dir = Direction::all_with_diagonals()
+        .into_iter()
+        .filter(|dir| diagonal || !dir.is_diagonal())
+        .filter(|dir| last_dir.is_none() || last_dir.unwrap().reverse() != *dir)
+        .filter(|dir| grid.is_cell_in_bounds(pos + Coordinate::from(*dir)))
+        .choose(rng);
 
+    if let Some(chosen_dir) = random_dir {
         let mut this_step = vec![chosen_dir];
         if depth == max_depth {
             this_step
@@ -27,6 +27,8 @@ pub fn generate_random_walk(
                 depth + 1,
                 max_depth,
                 Some(chosen_dir),
+                diagonal,
+                rng,
             );
 
             this_step.append(&mut other_steps);
@@ -36,3 +38,144 @@ pub fn generate_random_walk(
         Vec::new()
     }
 }
+
+// Every cell visited along the walk, starting with `pos` itself.
+pub fn get_walk_path(pos: Coordinate, dirs: &[Direction]) -> Vec<Coordinate> {
+    let mut current = pos;
+    let mut path = vec![pos];
+    dirs.iter().for_each(|dir| {
+        current = current + Coordinate::from(*dir);
+        path.push(current);
+    });
+
+    path
+}
+
+pub fn get_walk_destination(pos: Coordinate, dirs: &[Direction]) -> Coordinate {
+    dirs.iter()
+        .fold(pos, |accum, dir| accum + Coordinate::from(*dir))
+}
+
+// Mirrors how `resolve_moves` truncates a path: stop on the first free icy cell, then walk back
+// from the end of the path until a free cell is found. Returns `None` if the piece would not
+// move at all.
+pub fn get_truncated_destination(
+    pos: Coordinate,
+    dirs: &[Direction],
+    grid: &BattleGrid,
+) -> Option<Coordinate> {
+    let dirs = &dirs[..get_steps_before_ice(pos, dirs, grid)];
+    let mut final_location = get_walk_destination(pos, dirs);
+
+    for dir in dirs.iter().rev() {
+        if grid.is_cell_in_bounds(final_location)
+            && grid.get_status_at_coord(&final_location) == TileStatus::Empty
+        {
+            return Some(final_location);
+        }
+
+        final_location -= Coordinate::from(*dir);
+    }
+
+    None
+}
+
+// How many steps of the walk are taken before ice stops it.
+pub fn get_steps_before_ice(pos: Coordinate, dirs: &[Direction], grid: &BattleGrid) -> usize {
+    get_walk_path(pos, dirs)
+        .iter()
+        .skip(1)
+        .position(|coord| {
+            grid.is_cell_in_bounds(*coord)
+                && grid.get_status_at_coord(coord) == TileStatus::Empty
+                && grid.get_hazard_at(*coord).map(|hazard| hazard.kind) == Some(HazardKind::Ice)
+        })
+        .map_or(dirs.len(), |idx| idx + 1)
+}
+
+// Damage taken by a piece shoved into a wall, and by both pieces when it's shoved into another.
+pub const COLLISION_DAMAGE: i32 = 1;
+
+// Where a piece at `start` ends up when shoved `distance` cells straight away from `origin`, or
+// towards it for a negative distance. Returns every cell it passes through, starting with
+// `start`, and the cell it slammed into if something cut the move short. Being pulled up
+// against the origin itself isn't a collision.
+pub fn get_forced_path(
+    start: Coordinate,
+    origin: Coordinate,
+    distance: i32,
+    grid: &BattleGrid,
+) -> (Vec<Coordinate>, Option<Coordinate>) {
+    let mut path = vec![start];
+    let dir = match get_direction_towards(origin, start) {
+        Some(dir) if distance < 0 => dir.reverse(),
+        Some(dir) => dir,
+        None => return (path, None),
+    };
+
+    for _ in 0..distance.abs() {
+        let next = *path.last().unwrap() + Coordinate::from(dir);
+        if next == origin {
+            break;
+        }
+
+        if !grid.is_cell_in_bounds(next) || grid.get_status_at_coord(&next) != TileStatus::Empty {
+            return (path, Some(next));
+        }
+
+        path.push(next);
+    }
+
+    (path, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_stops_at_pieces_and_walls() {
+        let mut grid = BattleGrid::new();
+        let dragon = get_dragon_origin(&grid);
+        grid.set_status_at_coord(&Coordinate { x: 2, y: 0 }, TileStatus::Occupied);
+
+        let (path, collision) = get_forced_path(Coordinate { x: 2, y: 2 }, dragon, 3, &grid);
+        assert_eq!(
+            path,
+            vec![Coordinate { x: 2, y: 2 }, Coordinate { x: 2, y: 1 }]
+        );
+        assert_eq!(collision, Some(Coordinate { x: 2, y: 0 }));
+
+        let (path, collision) = get_forced_path(Coordinate { x: 1, y: 0 }, dragon, 1, &grid);
+        assert_eq!(path, vec![Coordinate { x: 1, y: 0 }]);
+        // Slammed into the top wall.
+        assert_eq!(collision, Some(Coordinate { x: 1, y: -1 }));
+    }
+
+    #[test]
+    fn test_ice_stops_a_walk() {
+        let mut grid = BattleGrid::new();
+        grid.place_hazard(Coordinate { x: 1, y: 0 }, HazardKind::Ice, 1);
+        grid.place_hazard(Coordinate { x: 2, y: 0 }, HazardKind::Ice, 1);
+        grid.set_status_at_coord(&Coordinate { x: 1, y: 0 }, TileStatus::Occupied);
+
+        // Occupied ice can't be stopped on, so the walk slides on to the next icy cell.
+        let destination = get_truncated_destination(
+            Coordinate { x: 0, y: 0 },
+            &[Direction::Right, Direction::Right, Direction::Right],
+            &grid,
+        );
+
+        assert_eq!(destination, Some(Coordinate { x: 2, y: 0 }));
+    }
+
+    #[test]
+    fn test_pull_stops_next_to_the_origin() {
+        let grid = BattleGrid::new();
+        let caster = Coordinate { x: 0, y: 2 };
+
+        let (path, collision) = get_forced_path(Coordinate { x: 3, y: 2 }, caster, -5, &grid);
+        assert_eq!(path.last(), Some(&Coordinate { x: 1, y: 2 }));
+        assert_eq!(collision, None);
+    }
+}
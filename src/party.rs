@@ -0,0 +1,172 @@
+use crate::prelude::*;
+
+pub const MAX_DIFFICULTY: u32 = 5;
+pub const MAX_PARTY_SIZE: usize = 5;
+// Elites start turning up from the second raid, and the champion leads the fourth and last.
+pub const ELITE_FLOOR: u32 = 1;
+pub const CHAMPION_FLOOR: u32 = 3;
+
+// How hard a raid is, from the run's chosen difficulty level and how many raids it has cleared.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    // Points to spend on heroes, see `generate_party`.
+    pub budget: i32,
+    pub health_bonus: i32,
+    pub init_bonus: i32,
+    // Chance from 0.0 to 1.0 that a hero goes for its hardest hitting ability instead of rolling.
+    pub aggression: f32,
+    // The best heroes the party generator may pick.
+    pub max_rank: HeroRank,
+    // Whether this is the final raid, led by a champion.
+    pub champion: bool,
+}
+
+impl Difficulty {
+    pub fn for_raid(level: u32, floor: u32) -> Self {
+        let level = level.min(MAX_DIFFICULTY) as i32;
+
+        Self {
+            budget: 8 + level + floor as i32 * 2,
+            health_bonus: level,
+            init_bonus: level / 2,
+            aggression: level as f32 / MAX_DIFFICULTY as f32,
+            max_rank: match floor >= ELITE_FLOOR {
+                true => HeroRank::Elite,
+                false => HeroRank::Normal,
+            },
+            champion: floor >= CHAMPION_FLOOR,
+        }
+    }
+}
+
+// Buys random heroes from the pool of `(hero id, cost)` until the budget runs out or the party is
+// full. Cheaper heroes fill in whatever the expensive ones leave over.
+pub fn generate_party(
+    pool: &[(i32, i32)],
+    budget: i32,
+    max_size: usize,
+    rng: &mut StdRng,
+) -> Vec<i32> {
+    let mut party = Vec::new();
+    let mut remaining = budget;
+
+    while party.len() < max_size {
+        let affordable = pool
+            .iter()
+            .filter(|(_, cost)| *cost > 0 && *cost <= remaining)
+            .collect::<Vec<&(i32, i32)>>();
+
+        match affordable.choose(rng) {
+            Some((id, cost)) => {
+                party.push(*id);
+                remaining -= cost;
+            }
+            None => break,
+        }
+    }
+
+    party
+}
+
+// Random starting cells for the party in the top two rows, furthest from the dragon.
+pub fn get_party_positions(grid: &BattleGrid, count: usize, rng: &mut StdRng) -> Vec<Coordinate> {
+    let mut cells = (0..2.min(grid.height))
+        .flat_map(|y| (0..grid.width).map(move |x| Coordinate { x, y }))
+        .collect::<Vec<Coordinate>>();
+    cells.shuffle(rng);
+    cells.truncate(count);
+
+    cells
+}
+
+// The free cells closest to `near`, for reinforcements to arrive on.
+pub fn get_reinforcement_positions(
+    grid: &BattleGrid,
+    near: Coordinate,
+    count: usize,
+) -> Vec<Coordinate> {
+    let mut cells = (0..grid.height)
+        .flat_map(|y| (0..grid.width).map(move |x| Coordinate { x, y }))
+        .filter(|coord| grid.get_status_at_coord(coord) == TileStatus::Empty)
+        .collect::<Vec<Coordinate>>();
+    cells.sort_by_key(|coord| (coord.manhattan_distance(near), coord.y, coord.x));
+    cells.truncate(count);
+
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difficulty_scales_with_level_and_floor() {
+        let easy = Difficulty::for_raid(0, 0);
+        let hard = Difficulty::for_raid(MAX_DIFFICULTY, 0);
+
+        assert_eq!(easy.health_bonus, 0);
+        assert_eq!(easy.aggression, 0.0);
+        assert_eq!(hard.aggression, 1.0);
+        assert!(hard.budget > easy.budget);
+        assert!(Difficulty::for_raid(0, 2).budget > easy.budget);
+        assert_eq!(Difficulty::for_raid(99, 0), hard);
+    }
+
+    #[test]
+    fn test_elites_and_champion_by_floor() {
+        assert_eq!(Difficulty::for_raid(0, 0).max_rank, HeroRank::Normal);
+        assert_eq!(
+            Difficulty::for_raid(0, ELITE_FLOOR).max_rank,
+            HeroRank::Elite
+        );
+        assert!(!Difficulty::for_raid(MAX_DIFFICULTY, CHAMPION_FLOOR - 1).champion);
+        assert!(Difficulty::for_raid(0, CHAMPION_FLOOR).champion);
+    }
+
+    #[test]
+    fn test_reinforcements_arrive_on_free_cells_nearby() {
+        let mut grid = BattleGrid::new();
+        let near = Coordinate { x: 2, y: 0 };
+        grid.set_status_at_coord(&near, TileStatus::Occupied);
+        grid.set_status_at_coord(&Coordinate { x: 1, y: 0 }, TileStatus::Obstacle);
+
+        let positions = get_reinforcement_positions(&grid, near, 2);
+
+        assert_eq!(
+            positions,
+            vec![Coordinate { x: 3, y: 0 }, Coordinate { x: 2, y: 1 }]
+        );
+    }
+
+    #[test]
+    fn test_party_stays_within_budget() {
+        let pool = [(1, 3), (2, 2), (3, 1), (4, 0)];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        (0..20).for_each(|_| {
+            let party = generate_party(&pool, 8, MAX_PARTY_SIZE, &mut rng);
+            let spent = party
+                .iter()
+                .map(|id| pool.iter().find(|(hero, _)| hero == id).unwrap().1)
+                .sum::<i32>();
+
+            assert!(spent <= 8);
+            // Anything left over is too little to buy even the cheapest hero.
+            assert!(spent == 8 || party.len() == MAX_PARTY_SIZE);
+            assert!(!party.contains(&4));
+        });
+    }
+
+    #[test]
+    fn test_party_positions_are_distinct() {
+        let grid = BattleGrid::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        let positions = get_party_positions(&grid, 5, &mut rng);
+
+        assert_eq!(positions.len(), 5);
+        positions.iter().enumerate().for_each(|(idx, coord)| {
+            assert!(coord.y < 2);
+            assert!(!positions[idx + 1..].contains(coord));
+        });
+    }
+}
@@ -0,0 +1,197 @@
+use crate::prelude::*;
+
+const LANE_SPACING: f32 = 10.0;
+const ARROW_LENGTH: f32 = 14.0;
+const ARROW_HALF_WIDTH: f32 = 8.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathSegment {
+    pub step: usize,
+    pub start: Vec2,
+    pub end: Vec2,
+    // The arrowhead at the end of the segment, as a triangle of (tip, left, right).
+    pub arrow: (Vec2, Vec2, Vec2),
+    // Segments past the point where the move gets truncated will not actually be walked.
+    pub blocked: bool,
+}
+
+impl PathSegment {
+    pub fn midpoint(&self) -> Vec2 {
+        (self.start + self.end) * 0.5
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovePathLayout {
+    pub segments: Vec<PathSegment>,
+    // Where the piece will actually stop if the path is cut short.
+    pub blocked_marker: Option<Vec2>,
+}
+
+// Offsets each mover's path sideways into its own lane so paths crossing the same cells stay
+// readable. `lane` should be unique per moving entity and less than `lane_count`.
+pub fn layout_move_path(
+    grid: &BattleGrid,
+    start: Coordinate,
+    dirs: &[Direction],
+    truncated_destination: Option<Coordinate>,
+    lane: usize,
+    lane_count: usize,
+) -> MovePathLayout {
+    let lane_offset = (lane as f32 - (lane_count.max(1) - 1) as f32 / 2.0) * LANE_SPACING;
+
+    // The number of steps actually taken before the move is blocked. The walk is truncated from
+    // the end, so the last visit to the destination is where it stops, as in `resolve_moves`.
+    let steps_taken = match truncated_destination {
+        Some(destination) => get_walk_path(start, dirs)
+            .iter()
+            .rposition(|coord| *coord == destination)
+            .unwrap_or(0),
+
+        None => 0,
+    };
+
+    let mut current = start;
+    let segments = dirs
+        .iter()
+        .enumerate()
+        .map(|(idx, dir)| {
+            let next = current + Coordinate::from(*dir);
+            let step_vec = Coordinate::from(*dir);
+            let forward = Vec2::new(step_vec.x as f32, step_vec.y as f32).normalize_or_zero();
+            let perpendicular = forward.perp();
+
+            let start_pos =
+                grid.get_cell_center(current.x, current.y) + perpendicular * lane_offset;
+            let end_pos = grid.get_cell_center(next.x, next.y) + perpendicular * lane_offset;
+
+            let base = end_pos - forward * ARROW_LENGTH;
+            let arrow = (
+                end_pos,
+                base + perpendicular * ARROW_HALF_WIDTH,
+                base - perpendicular * ARROW_HALF_WIDTH,
+            );
+
+            current = next;
+
+            PathSegment {
+                step: idx + 1,
+                start: start_pos,
+                end: end_pos,
+                arrow,
+                blocked: idx >= steps_taken,
+            }
+        })
+        .collect::<Vec<PathSegment>>();
+
+    let blocked_marker = match steps_taken < dirs.len() {
+        true => {
+            let stop = truncated_destination.unwrap_or(start);
+            let perpendicular = segments
+                .get(steps_taken)
+                .map(|segment| (segment.end - segment.start).normalize_or_zero().perp())
+                .unwrap_or(Vec2::ZERO);
+
+            Some(grid.get_cell_center(stop.x, stop.y) + perpendicular * lane_offset)
+        }
+
+        false => None,
+    };
+
+    MovePathLayout {
+        segments,
+        blocked_marker,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_lane_runs_through_cell_centers() {
+        let grid = BattleGrid::new();
+        let start = Coordinate { x: 1, y: 1 };
+        let dirs = [Direction::Right, Direction::Down];
+
+        let layout = layout_move_path(&grid, start, &dirs, Some(Coordinate { x: 2, y: 2 }), 0, 1);
+
+        assert_eq!(layout.segments.len(), 2);
+        assert_eq!(layout.segments[0].start, grid.get_cell_center(1, 1));
+        assert_eq!(layout.segments[1].end, grid.get_cell_center(2, 2));
+        assert_eq!(layout.segments[1].step, 2);
+        assert!(layout.segments.iter().all(|segment| !segment.blocked));
+        assert_eq!(layout.blocked_marker, None);
+    }
+
+    #[test]
+    fn test_lanes_are_offset_perpendicular_to_movement() {
+        let grid = BattleGrid::new();
+        let start = Coordinate { x: 0, y: 2 };
+        let dirs = [Direction::Right];
+        let destination = Some(Coordinate { x: 1, y: 2 });
+
+        let first = layout_move_path(&grid, start, &dirs, destination, 0, 2);
+        let second = layout_move_path(&grid, start, &dirs, destination, 1, 2);
+
+        let first_start = first.segments[0].start;
+        let second_start = second.segments[0].start;
+
+        assert_eq!(first_start.x, second_start.x);
+        assert_ne!(first_start.y, second_start.y);
+        assert_eq!(
+            (first_start + second_start) * 0.5,
+            grid.get_cell_center(0, 2)
+        );
+    }
+
+    #[test]
+    fn test_truncated_path_marks_blocked_steps() {
+        let grid = BattleGrid::new();
+        let start = Coordinate { x: 0, y: 0 };
+        let dirs = [Direction::Right, Direction::Right, Direction::Down];
+
+        let layout = layout_move_path(&grid, start, &dirs, Some(Coordinate { x: 1, y: 0 }), 0, 1);
+
+        let blocked = layout
+            .segments
+            .iter()
+            .map(|segment| segment.blocked)
+            .collect::<Vec<bool>>();
+        assert_eq!(blocked, vec![false, true, true]);
+        assert_eq!(layout.blocked_marker, Some(grid.get_cell_center(1, 0)));
+    }
+
+    #[test]
+    fn test_revisited_cell_stops_at_the_last_visit() {
+        let grid = BattleGrid::new();
+        let start = Coordinate { x: 1, y: 1 };
+        let dirs = [
+            Direction::Right,
+            Direction::Left,
+            Direction::Right,
+            Direction::Down,
+        ];
+
+        let layout = layout_move_path(&grid, start, &dirs, Some(Coordinate { x: 2, y: 1 }), 0, 1);
+
+        let blocked = layout
+            .segments
+            .iter()
+            .map(|segment| segment.blocked)
+            .collect::<Vec<bool>>();
+        assert_eq!(blocked, vec![false, false, false, true]);
+        assert_eq!(layout.blocked_marker, Some(grid.get_cell_center(2, 1)));
+    }
+
+    #[test]
+    fn test_fully_blocked_path_stops_at_start() {
+        let grid = BattleGrid::new();
+        let start = Coordinate { x: 3, y: 3 };
+
+        let layout = layout_move_path(&grid, start, &[Direction::Up], None, 0, 1);
+
+        assert!(layout.segments[0].blocked);
+        assert_eq!(layout.blocked_marker, Some(grid.get_cell_center(3, 3)));
+    }
+}
@@ -0,0 +1,126 @@
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PhaseSpeed {
+    Instant,
+    Normal,
+    Slow,
+}
+
+impl PhaseSpeed {
+    // Seconds between automatic phase steps.
+    pub fn step_duration(&self) -> f32 {
+        match self {
+            PhaseSpeed::Instant => 0.0,
+            PhaseSpeed::Normal => 0.5,
+            PhaseSpeed::Slow => 1.0,
+        }
+    }
+}
+
+const FAST_FORWARD_MULTIPLIER: f32 = 4.0;
+
+// Paces automatic phase advancement, AI declarations and resolution. Paused timers only move
+// forward when a single step is requested.
+pub struct PhaseTimer {
+    pub speed: PhaseSpeed,
+    pub paused: bool,
+    pub fast_forward: bool,
+    elapsed: f32,
+    step_requested: bool,
+}
+
+impl PhaseTimer {
+    pub fn new(speed: PhaseSpeed) -> Self {
+        Self {
+            speed,
+            paused: false,
+            fast_forward: false,
+            elapsed: 0.0,
+            step_requested: false,
+        }
+    }
+
+    pub fn step_duration(&self) -> f32 {
+        match self.fast_forward {
+            true => self.speed.step_duration() / FAST_FORWARD_MULTIPLIER,
+            false => self.speed.step_duration(),
+        }
+    }
+
+    // Advances the timer and returns how many steps are due. Instant speed allows one step per
+    // tick so that every action still gets a frame.
+    pub fn tick(&mut self, dt: f32) -> u32 {
+        if std::mem::take(&mut self.step_requested) {
+            self.elapsed = 0.0;
+            return 1;
+        }
+
+        if self.paused {
+            return 0;
+        }
+
+        let duration = self.step_duration();
+        if duration <= 0.0 {
+            return 1;
+        }
+
+        self.elapsed += dt;
+        let steps = (self.elapsed / duration).floor();
+        self.elapsed -= steps * duration;
+
+        steps as u32
+    }
+
+    pub fn request_step(&mut self) {
+        self.step_requested = true;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_speed_steps_every_half_second() {
+        let mut timer = PhaseTimer::new(PhaseSpeed::Normal);
+
+        assert_eq!(timer.tick(0.3), 0);
+        assert_eq!(timer.tick(0.3), 1);
+        assert_eq!(timer.tick(1.0), 2);
+    }
+
+    #[test]
+    fn test_instant_speed_steps_every_tick() {
+        let mut timer = PhaseTimer::new(PhaseSpeed::Instant);
+
+        assert_eq!(timer.tick(0.0), 1);
+        assert_eq!(timer.tick(10.0), 1);
+    }
+
+    #[test]
+    fn test_fast_forward_speeds_up_steps() {
+        let mut timer = PhaseTimer::new(PhaseSpeed::Slow);
+        timer.fast_forward = true;
+
+        assert_eq!(timer.tick(0.5), 2);
+    }
+
+    #[test]
+    fn test_paused_timer_only_moves_on_requested_step() {
+        let mut timer = PhaseTimer::new(PhaseSpeed::Normal);
+        timer.toggle_pause();
+
+        assert_eq!(timer.tick(5.0), 0);
+
+        timer.request_step();
+        assert_eq!(timer.tick(0.0), 1);
+        assert_eq!(timer.tick(5.0), 0);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::prelude::*;
+use rusqlite::Connection;
+
+pub struct RelicDB {
+    connection: Connection,
+}
+
+impl RelicDB {
+    pub fn new() -> Self {
+        RelicDB {
+            connection: Connection::open("./assets/relics.db").unwrap(),
+        }
+    }
+
+    pub fn get_relic_from_id(&mut self, id: i32) -> RelicData {
+        let mut stmt = self
+            .connection
+            .prepare(&format!(
+                "SELECT RelicID, Name, Trigger, Effects, Description, Icon FROM Relics WHERE RelicID={}",
+                id
+            ))
+            .unwrap();
+
+        let mut relic_iter = stmt
+            .query_map([], |row| {
+                Ok(RelicData {
+                    id: row.get(0).unwrap(),
+                    name: row.get(1).unwrap(),
+                    trigger: row.get(2).unwrap(),
+                    effects: row.get(3).unwrap(),
+                    description: row.get(4).unwrap(),
+                    icon: row.get(5).unwrap(),
+                })
+            })
+            .unwrap();
+
+        relic_iter.nth(0).unwrap().unwrap()
+    }
+
+    pub fn get_relic_ids(&mut self) -> Vec<i32> {
+        let mut stmt = self
+            .connection
+            .prepare("SELECT RelicID FROM Relics ORDER BY RelicID")
+            .unwrap();
+
+        let id_iter = stmt.query_map([], |row| row.get(0)).unwrap();
+
+        id_iter
+            .collect::<Result<Vec<i32>, rusqlite::Error>>()
+            .unwrap()
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RelicData {
+    id: i32,
+    pub name: String,
+    // See `parse_relic_trigger`.
+    trigger: String,
+    effects: String,
+    description: String,
+    // Path of the relic's icon within the assets folder.
+    icon: Option<String>,
+}
+
+impl RelicData {
+    pub fn to_relic(&self) -> Result<Relic, String> {
+        Ok(Relic {
+            name: self.name.to_owned(),
+            description: self.description.to_owned(),
+            trigger: parse_relic_trigger(&self.trigger)?,
+            effects: get_effects_from_text(self.effects.to_owned())?,
+            icon: self.icon.as_ref().map(|path| Sprite {
+                path: path.to_owned(),
+            }),
+        })
+    }
+}
+
+// When a relic fires during combat.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelicTrigger {
+    StartOfCombat,
+    StartOfRound,
+    CardPlayed,
+    HeroDeath,
+}
+
+fn parse_relic_trigger(val: &str) -> Result<RelicTrigger, String> {
+    match val.trim() {
+        "start_of_combat" => Ok(RelicTrigger::StartOfCombat),
+        "start_of_round" => Ok(RelicTrigger::StartOfRound),
+        "card_played" => Ok(RelicTrigger::CardPlayed),
+        "hero_death" => Ok(RelicTrigger::HeroDeath),
+        other => Err(format!("Unknown relic trigger: {other}")),
+    }
+}
+
+// A passive bonus carried for the whole run. Its effects use the same language as cards, cast
+// from the dragon.
+pub struct Relic {
+    pub name: String,
+    pub description: String,
+    pub trigger: RelicTrigger,
+    pub effects: Vec<Effect>,
+    pub icon: Option<Sprite>,
+}
+
+// The relics the player has brought into the current fight.
+pub struct Relics {
+    pub list: Vec<Relic>,
+}
+
+impl Relics {
+    pub fn new() -> Self {
+        Self { list: Vec::new() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relic_by_id() {
+        let mut db = RelicDB::new();
+
+        let relic = db.get_relic_from_id(2).to_relic().unwrap();
+
+        assert_eq!(relic.name, "Smouldering Scale");
+        assert_eq!(relic.trigger, RelicTrigger::StartOfRound);
+        assert_eq!(
+            relic.effects,
+            vec![
+                Effect::DefineTarget(TargetShape::Single),
+                Effect::DealDamage(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_every_relic_parses() {
+        let mut db = RelicDB::new();
+
+        db.get_relic_ids().iter().for_each(|id| {
+            assert!(db.get_relic_from_id(*id).to_relic().is_ok());
+        });
+        assert!(parse_relic_trigger("on_blink").is_err());
+    }
+}
@@ -0,0 +1,198 @@
+use crate::prelude::*;
+
+pub const SAVE_PATH: &str = "./run.sav";
+
+// Everything that carries over between fights: the seed every random roll is drawn from, the
+// player's health and their relics. Saved as `name = value` lines when the player quits mid-run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub seed: u64,
+    // Unset for a fresh run, so the player starts at full health.
+    pub player_health: Option<i32>,
+    // Ids into `RelicDB`. A fresh run has none until its first fight hands out a starting relic.
+    pub relics: Vec<i32>,
+    // Picked on the title screen, from 0 up to `MAX_DIFFICULTY`.
+    pub difficulty: u32,
+    // How many raids the dragon has already seen off this run.
+    pub floor: u32,
+}
+
+impl Run {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            player_health: None,
+            relics: Vec::new(),
+            difficulty: 0,
+            floor: 0,
+        }
+    }
+
+    pub fn random_seed() -> u64 {
+        thread_rng().gen_range(0..1_000_000)
+    }
+
+    // Each raid rolls differently, but replaying a floor on the same seed rolls the same.
+    pub fn build_rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed.wrapping_add(self.floor as u64))
+    }
+
+    pub fn load(path: &str) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+
+        match parse_run(&text) {
+            Ok(run) => Some(run),
+            Err(err) => {
+                eprintln!("Ignoring saved run in {path}: {err}");
+                None
+            }
+        }
+    }
+
+    pub fn has_save(path: &str) -> bool {
+        std::path::Path::new(path).exists()
+    }
+
+    pub fn save(&self, path: &str) {
+        if let Err(err) = std::fs::write(path, self.to_save()) {
+            eprintln!("Failed to save the run to {path}: {err}");
+        }
+    }
+
+    pub fn delete_save(path: &str) {
+        if Self::has_save(path) {
+            if let Err(err) = std::fs::remove_file(path) {
+                eprintln!("Failed to delete the saved run at {path}: {err}");
+            }
+        }
+    }
+
+    fn to_save(&self) -> String {
+        let mut text = format!(
+            "seed = {}\ndifficulty = {}\nfloor = {}\n",
+            self.seed, self.difficulty, self.floor
+        );
+        if let Some(health) = self.player_health {
+            text.push_str(&format!("player_health = {health}\n"));
+        }
+        if !self.relics.is_empty() {
+            let relics = self
+                .relics
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<String>>();
+            text.push_str(&format!("relics = {}\n", relics.join(", ")));
+        }
+
+        text
+    }
+}
+
+fn parse_run(text: &str) -> Result<Run, String> {
+    let mut seed = None;
+    let mut player_health = None;
+    let mut relics = Vec::new();
+    let mut difficulty = 0;
+    let mut floor = 0;
+
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .try_for_each(|line| {
+            let (name, value) = line
+                .split_once('=')
+                .ok_or(format!("Expected `name = value` but got: {line}"))?;
+            let value = value.trim();
+
+            match name.trim() {
+                "seed" => {
+                    seed = Some(
+                        value
+                            .parse::<u64>()
+                            .map_err(|_| format!("Invalid seed: {value}"))?,
+                    )
+                }
+                "player_health" => {
+                    player_health = Some(
+                        value
+                            .parse::<i32>()
+                            .map_err(|_| format!("Invalid health: {value}"))?,
+                    )
+                }
+                "difficulty" => {
+                    difficulty = value
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid difficulty: {value}"))?
+                        .min(MAX_DIFFICULTY)
+                }
+                "floor" => {
+                    floor = value
+                        .parse::<u32>()
+                        .map_err(|_| format!("Invalid floor: {value}"))?
+                }
+                "relics" => {
+                    relics = value
+                        .split(',')
+                        .map(|id| id.trim())
+                        .filter(|id| !id.is_empty())
+                        .map(|id| id.parse::<i32>())
+                        .collect::<Result<Vec<i32>, _>>()
+                        .map_err(|_| format!("Invalid relics: {value}"))?
+                }
+                other => return Err(format!("Unknown field: {other}")),
+            }
+
+            Ok(())
+        })?;
+
+    Ok(Run {
+        seed: seed.ok_or("Missing seed")?,
+        player_health,
+        relics,
+        difficulty,
+        floor,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_round_trip() {
+        let run = Run {
+            seed: 1234,
+            player_health: Some(17),
+            relics: vec![2, 4],
+            difficulty: 3,
+            floor: 2,
+        };
+
+        assert_eq!(parse_run(&run.to_save()), Ok(run));
+        assert_eq!(parse_run(&Run::new(5).to_save()), Ok(Run::new(5)));
+    }
+
+    #[test]
+    fn test_parse_run_rejects_bad_saves() {
+        assert!(parse_run("player_health = 3").is_err());
+        assert!(parse_run("seed = -1").is_err());
+        assert!(parse_run("seed = 1\ngold = 5").is_err());
+        assert!(parse_run("seed = 1\nrelics = 1, shiny").is_err());
+        assert!(parse_run("seed = 1\nfloor = -2").is_err());
+    }
+
+    #[test]
+    fn test_difficulty_is_capped() {
+        let run = parse_run("seed = 1\ndifficulty = 40").unwrap();
+        assert_eq!(run.difficulty, MAX_DIFFICULTY);
+    }
+
+    #[test]
+    fn test_same_seed_same_rolls() {
+        let run = Run::new(42);
+        let mut first = run.build_rng();
+        let mut second = run.build_rng();
+
+        (0..10).for_each(|_| assert_eq!(first.gen::<u32>(), second.gen::<u32>()));
+    }
+}
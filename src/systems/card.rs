@@ -1,74 +1,927 @@
 use crate::prelude::*;
 
-const CARD_HEIGHT: f32 = 200.0;
-const CARD_WIDTH: f32 = CARD_HEIGHT * 0.7;
+const QUEUED_CARD_RAISE: f32 = 40.0;
+// How far the mouse can wander while pressing a card before it counts as a drag.
+const DRAG_THRESHOLD: f32 = 8.0;
 
-const HAND_ZONE_Y: f32 = 600.0;
-const HAND_ZONE_SPAN: std::ops::Range<f32> = 700.0..1200.0;
-const HAND_ZONE_WIDTH: f32 = HAND_ZONE_SPAN.end - HAND_ZONE_SPAN.start;
+#[system]
+#[read_component(Card)]
+#[read_component(Cost)]
+#[read_component(Message)]
+#[read_component(PlayCard)]
+#[read_component(Sprite)]
+#[read_component(Keywords)]
+pub fn render_hand(
+    ecs: &SubWorld,
+    #[resource] card_zones: &CardZones,
+    #[resource] cursor: &PlayerCursor,
+    #[resource] layout: &Layout,
+    #[resource] textures: &mut TextureAssets,
+) {
+    let mouse = Vec2::from(mouse_position());
+    let (placements, focused) = get_hand_placements(ecs, card_zones, cursor, layout, mouse);
+
+    draw_debug_hand_zone(layout);
+    draw_text(
+        &format!(
+            "Deck: {}  Discard: {}  Exhausted: {}",
+            card_zones.deck.len(),
+            card_zones.discard.len(),
+            card_zones.exhaust.len()
+        ),
+        layout.hand_zone_start,
+        layout.hand_zone_y - layout.card_size.y * 0.6,
+        24.0 * layout.scale,
+        WHITE,
+    );
 
-const CARD_MAX_ARC_DOWN: f32 = 64.0;
+    // The focused card is drawn last so it sits above its neighbours.
+    let draw_order = (0..card_zones.hand.len())
+        .filter(|idx| Some(*idx) != focused)
+        .chain(focused);
 
-#[system]
-pub fn render_hand(#[resource] card_zones: &CardZones) {
-    let cards_in_hand = 10;
+    draw_order.for_each(|idx| {
+        let card_entity = card_zones.hand[idx];
+        let placement = placements[idx];
+        let queued_order = <(&Message, &PlayCard)>::query()
+            .iter(ecs)
+            .find(|(_, play)| play.card == card_entity)
+            .map(|(_, play)| play.order);
+
+        let (name, cost, keywords, art) = match ecs.entry_ref(card_entity) {
+            Ok(entry) => (
+                entry
+                    .get_component::<Card>()
+                    .map(|card| card.name.clone())
+                    .unwrap_or_default(),
+                entry
+                    .get_component::<Cost>()
+                    .map(|cost| cost.amount)
+                    .unwrap_or(0),
+                get_keyword_text(entry.get_component::<Keywords>().ok()),
+                textures.get_sprite(entry.get_component::<Sprite>().ok()),
+            ),
 
-    draw_debug_hand_zone();
+            Err(_) => return,
+        };
+
+        let highlight = match (queued_order, cursor.selected_card == Some(idx)) {
+            (Some(_), _) => Some(GOLD),
+            (None, true) => Some(SKYBLUE),
+            (None, false) => None,
+        };
 
-    (0..cards_in_hand).for_each(|idx| {
-        let card_pos = calculate_card_pos(idx, cards_in_hand);
-        // TODO: ADJUST TO TOP LEFT WHEN YOU UNCOMMENT
         render_card(
-            calculate_card_pos(idx, cards_in_hand) - Vec2::new(CARD_WIDTH / 2.0, CARD_HEIGHT / 2.0),
-            "Blargasnarg".to_string(),
-            69,
-            "The Blargasnarg is a vicious beast that cannot be vanquished.".to_string(),
+            layout,
+            placement,
+            name,
+            cost,
+            keywords,
+            art,
+            highlight,
+            queued_order,
         );
     });
 }
 
-fn draw_debug_hand_zone() {
+#[system]
+#[read_component(Player)]
+#[read_component(Cost)]
+#[read_component(Message)]
+#[read_component(Source)]
+#[read_component(PlayCard)]
+#[read_component(ActionPoints)]
+#[read_component(ActionDeclarationFinished)]
+#[read_component(Effects)]
+#[read_component(Taunt)]
+#[read_component(Coordinate)]
+pub fn render_declaration_ui(
+    ecs: &SubWorld,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] grid: &BattleGrid,
+    #[resource] cursor: &PlayerCursor,
+    #[resource] card_zones: &CardZones,
+    #[resource] layout: &Layout,
+) {
+    <(&Message, &PlayCard)>::query()
+        .iter(ecs)
+        .for_each(|(_, play)| {
+            let tl = grid.get_cell_tl(play.target.x, play.target.y);
+            draw_rectangle_lines(tl.x, tl.y, grid.grid_size, grid.grid_size, 4.0, GOLD);
+            draw_text(
+                &(play.order + 1).to_string(),
+                tl.x + 6.0,
+                tl.y + grid.grid_size - 6.0,
+                28.0,
+                GOLD,
+            );
+        });
+
+    let player = match get_declaring_player(ecs, turn_tracker) {
+        Some(player) => player,
+        None => return,
+    };
+
+    // Preview what the chosen card would hit from wherever it's currently aimed.
+    let aiming = cursor.targeting || cursor.drag_origin.is_some();
+    let aim = match cursor.targeting {
+        true => Some(cursor.grid_cursor),
+        false => grid.get_cell_at_screen_pos(Vec2::from(mouse_position())),
+    };
+    let effects = cursor
+        .selected_card
+        .and_then(|idx| card_zones.hand.get(idx))
+        .and_then(|card| ecs.entry_ref(*card).ok())
+        .and_then(|entry| entry.into_component::<Effects>().ok());
+
+    if let (true, Some(aim), Some(effects)) = (aiming, aim, effects) {
+        let origin = get_dragon_origin(grid);
+        let aim = get_taunted_aim(&effects.list, origin, aim, &get_taunters(ecs), grid);
+        interpret_effects(&effects.list, origin, aim, grid)
+            .targets
+            .iter()
+            .for_each(|coord| {
+                let tl = grid.get_cell_tl(coord.x, coord.y);
+                draw_rectangle(
+                    tl.x,
+                    tl.y,
+                    grid.grid_size,
+                    grid.grid_size,
+                    Color::new(SKYBLUE.r, SKYBLUE.g, SKYBLUE.b, 0.35),
+                );
+            });
+    }
+
+    if cursor.targeting {
+        let tl = grid.get_cell_tl(cursor.grid_cursor.x, cursor.grid_cursor.y);
+        draw_rectangle_lines(tl.x, tl.y, grid.grid_size, grid.grid_size, 6.0, SKYBLUE);
+    }
+
+    let (used, available) = get_player_energy(ecs, player);
+    draw_text(
+        &format!("Energy: {}/{}", available - used, available),
+        layout.end_turn_button.x,
+        layout.end_turn_button.y - 12.0 * layout.scale,
+        32.0 * layout.scale,
+        SKYBLUE,
+    );
+
+    render_button(layout.end_turn_button, "End Turn", layout.scale);
+    render_button(layout.undo_button, "Undo", layout.scale);
+}
+
+#[system]
+#[read_component(Player)]
+#[read_component(Cost)]
+#[read_component(Message)]
+#[read_component(Source)]
+#[read_component(PlayCard)]
+#[read_component(ActionPoints)]
+#[read_component(ActionDeclarationFinished)]
+#[read_component(Effects)]
+#[read_component(Taunt)]
+#[read_component(Coordinate)]
+#[allow(clippy::too_many_arguments)]
+pub fn declare_player_action(
+    ecs: &SubWorld,
+    commands: &mut CommandBuffer,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] card_zones: &CardZones,
+    #[resource] grid: &BattleGrid,
+    #[resource] bindings: &InputBindings,
+    #[resource] cursor: &mut PlayerCursor,
+    #[resource] layout: &Layout,
+    #[resource] events: &mut CombatEvents,
+) {
+    let player = match get_declaring_player(ecs, turn_tracker) {
+        Some(player) => player,
+        None => return,
+    };
+
+    let clicked = is_mouse_button_pressed(MouseButton::Left);
+    let mouse = Vec2::from(mouse_position());
+
+    if bindings.is_pressed(InputAction::EndTurn)
+        || (clicked && layout.end_turn_button.contains(mouse))
+    {
+        commands.push((
+            (),
+            Message,
+            ActionDeclarationFinished,
+            Source { entity: player },
+            Round,
+        ));
+        cursor.selected_card = None;
+        cursor.targeting = false;
+        return;
+    }
+
+    let mut declarations = <(Entity, &Message, &Source, &PlayCard)>::query()
+        .iter(ecs)
+        .filter(|(_, _, src, _)| src.entity == player)
+        .map(|(entity, _, _, play)| (*entity, play.order))
+        .collect::<Vec<(Entity, usize)>>();
+    declarations.sort_by_key(|(_, order)| *order);
+
+    if bindings.is_pressed(InputAction::Undo) || (clicked && layout.undo_button.contains(mouse)) {
+        if let Some((last, _)) = declarations.last() {
+            commands.remove(*last);
+        }
+        return;
+    }
+
+    if bindings.is_pressed(InputAction::Cancel) || is_mouse_button_pressed(MouseButton::Right) {
+        cursor.cancel();
+        return;
+    }
+
+    let hand_size = card_zones.hand.len();
+    [(InputAction::NextCard, 1), (InputAction::PrevCard, -1)]
+        .iter()
+        .filter(|(action, _)| bindings.is_pressed(*action))
+        .for_each(|(_, offset)| {
+            cursor.cycle_card(*offset, hand_size);
+            cursor.targeting = false;
+        });
+
+    if cursor.targeting {
+        [
+            (InputAction::CursorUp, Direction::Up),
+            (InputAction::CursorDown, Direction::Down),
+            (InputAction::CursorLeft, Direction::Left),
+            (InputAction::CursorRight, Direction::Right),
+        ]
+        .iter()
+        .filter(|(action, _)| bindings.is_pressed(*action))
+        .for_each(|(_, dir)| cursor.move_grid_cursor(*dir, grid));
+    }
+
+    let (placements, _) = get_hand_placements(ecs, card_zones, cursor, layout, mouse);
+    if let Some(idx) = clicked
+        .then(|| get_card_at_point(&placements, layout.card_size, mouse))
+        .flatten()
+    {
+        cursor.selected_card = Some(idx);
+        cursor.targeting = false;
+        cursor.drag_origin = Some(mouse);
+        return;
+    }
+
+    let mut dropped = false;
+    if is_mouse_button_released(MouseButton::Left) {
+        if let Some(origin) = cursor.drag_origin.take() {
+            match grid.get_cell_at_screen_pos(mouse) {
+                // Dropped onto the board: play it there.
+                Some(cell) => {
+                    cursor.grid_cursor = cell;
+                    cursor.targeting = true;
+                    dropped = true;
+                }
+
+                // Barely moved, so treat it as a click and pick a target next.
+                None if mouse.distance(origin) < DRAG_THRESHOLD * layout.scale => {
+                    cursor.targeting = true;
+                    return;
+                }
+
+                None => {
+                    cursor.selected_card = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    let clicked_cell = match clicked {
+        true => grid.get_cell_at_screen_pos(mouse),
+        false => None,
+    };
+    if let (true, Some(cell)) = (cursor.targeting, clicked_cell) {
+        cursor.grid_cursor = cell;
+    }
+
+    let confirmed = bindings.is_pressed(InputAction::Confirm) || clicked_cell.is_some() || dropped;
+    if !confirmed {
+        return;
+    }
+
+    if !cursor.targeting {
+        cursor.targeting = cursor.selected_card.is_some();
+        return;
+    }
+
+    if let Some(card) = cursor
+        .selected_card
+        .and_then(|idx| card_zones.hand.get(idx))
+    {
+        let (used, available) = get_player_energy(ecs, player);
+        if !is_card_queued(ecs, card) && used + get_card_cost(ecs, card) <= available {
+            events.push(CombatEvent::CardPlayed {
+                entity: player,
+                card: *card,
+            });
+
+            let origin = get_dragon_origin(grid);
+            let effects = ecs
+                .entry_ref(*card)
+                .ok()
+                .and_then(|entry| entry.into_component::<Effects>().ok());
+            let aim = match effects {
+                Some(effects) => get_taunted_aim(
+                    &effects.list,
+                    origin,
+                    cursor.grid_cursor,
+                    &get_taunters(ecs),
+                    grid,
+                ),
+                None => cursor.grid_cursor,
+            };
+
+            let play = commands.push((
+                (),
+                Message,
+                Source { entity: player },
+                PlayCard {
+                    card: *card,
+                    order: declarations.last().map_or(0, |(_, order)| order + 1),
+                    target: aim,
+                },
+                Round,
+            ));
+
+            // Damage, shoves, hazards and delays ride along on the play itself, so undoing the play
+            // takes them back.
+            if let Some(effects) = effects {
+                let outcome = interpret_effects(&effects.list, origin, aim, grid);
+                if outcome.forced_move != 0 {
+                    commands.add_component(
+                        play,
+                        ForcedMove {
+                            targets: outcome.targets.clone(),
+                            origin,
+                            distance: outcome.forced_move,
+                        },
+                    );
+                }
+                if !outcome.initiative_changes.is_empty() {
+                    commands.add_component(
+                        play,
+                        InitiativeChange {
+                            targets: outcome.targets.clone(),
+                            changes: outcome.initiative_changes.clone(),
+                        },
+                    );
+                }
+                if !outcome.hazards.is_empty() {
+                    commands.add_component(
+                        play,
+                        PlaceHazards {
+                            targets: outcome.targets.clone(),
+                            hazards: outcome.hazards.clone(),
+                        },
+                    );
+                }
+                if outcome.damage > 0 {
+                    commands.add_component(
+                        play,
+                        Attack {
+                            targets: outcome.targets,
+                            damage: outcome.damage,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    cursor.selected_card = None;
+    cursor.targeting = false;
+}
+
+// Played cards leave the hand as the player's turn resolves, applying any card changes they
+// make. Plays without an attack linger until the round's messages are cleared, so this has to
+// cope with seeing them again.
+#[system(for_each)]
+#[read_component(Keywords)]
+#[read_component(Effects)]
+#[read_component(Message)]
+#[read_component(PlayCard)]
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_card_plays(
+    ecs: &SubWorld,
+    commands: &mut CommandBuffer,
+    _msg: &Message,
+    src: &Source,
+    play: &PlayCard,
+    #[resource] card_zones: &mut CardZones,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] db: &mut CardDB,
+    #[resource] rng: &mut StdRng,
+    #[resource] events: &mut CombatEvents,
+) {
+    if !turn_tracker.is_current_combatant(&src.entity) {
+        return;
+    }
+
+    if card_zones.play(play.card, &get_card_keywords(ecs, &play.card)) {
+        events.push(CombatEvent::CardResolved { card: play.card });
+
+        let changes = ecs
+            .entry_ref(play.card)
+            .ok()
+            .and_then(|entry| {
+                entry.get_component::<Effects>().ok().map(|effects| {
+                    effects
+                        .list
+                        .iter()
+                        .filter_map(|effect| match effect {
+                            Effect::ChangeCards(change) => Some(*change),
+                            _ => None,
+                        })
+                        .collect::<Vec<CardChange>>()
+                })
+            })
+            .unwrap_or_default();
+
+        let queued = get_queued_cards(ecs);
+        apply_card_changes(
+            &changes,
+            turn_tracker.turn_state,
+            &queued,
+            commands,
+            card_zones,
+            db,
+            rng,
+        );
+    }
+}
+
+#[system(for_each)]
+#[read_component(Message)]
+#[read_component(PlayCard)]
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_card_changes(
+    ecs: &SubWorld,
+    commands: &mut CommandBuffer,
+    m_entity: &Entity,
+    _msg: &Message,
+    src: &Source,
+    changes: &CardChanges,
+    #[resource] card_zones: &mut CardZones,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] db: &mut CardDB,
+    #[resource] rng: &mut StdRng,
+) {
+    if !turn_tracker.is_current_combatant(&src.entity) {
+        return;
+    }
+
+    let queued = get_queued_cards(ecs);
+    apply_card_changes(
+        &changes.list,
+        turn_tracker.turn_state,
+        &queued,
+        commands,
+        card_zones,
+        db,
+        rng,
+    );
+    commands.remove(*m_entity);
+}
+
+// Hand changes only take effect while the player is declaring. At any other time the hand is
+// about to be cycled, so they're held in `pending_changes` until the next declare phase. Cards
+// in `queued` are waiting to be played and are never discarded or transformed.
+pub fn apply_card_changes(
+    changes: &[CardChange],
+    turn_state: TurnState,
+    queued: &[Entity],
+    commands: &mut CommandBuffer,
+    card_zones: &mut CardZones,
+    db: &mut CardDB,
+    rng: &mut StdRng,
+) {
+    changes.iter().for_each(|change| match *change {
+        change if change.acts_on_hand() && turn_state != TurnState::DeclarePhase => {
+            card_zones.pending_changes.push(change)
+        }
+
+        CardChange::Draw(count) => card_zones.draw(count.max(0) as usize, rng),
+        CardChange::Discard(count) => card_zones.discard_random(count.max(0) as usize, queued, rng),
+
+        CardChange::CreateTemporary(id) => {
+            let data = db.get_card_from_id(id);
+            let card = data
+                .spawn_as_entity(commands)
+                .expect("Failed to Spawn a Card.");
+
+            let mut keywords = data.get_keywords().unwrap_or_default();
+            keywords.extend([Keyword::Exhaust, Keyword::Ethereal]);
+            commands.add_component(card, Keywords { list: keywords });
+            card_zones.hand.push(card);
+        }
+
+        CardChange::AddToDeck(id) => {
+            let card = db
+                .get_card_from_id(id)
+                .spawn_as_entity(commands)
+                .expect("Failed to Spawn a Card.");
+            card_zones.shuffle_into_deck(card, rng);
+        }
+
+        CardChange::Transform(count) => {
+            let ids = db.get_playable_card_ids();
+            let mut candidates = card_zones
+                .hand
+                .iter()
+                .filter(|card| !queued.contains(card))
+                .copied()
+                .collect::<Vec<Entity>>();
+            candidates.shuffle(rng);
+
+            for old in candidates.into_iter().take(count.max(0) as usize) {
+                if let Some(id) = ids.choose(rng) {
+                    let new = db
+                        .get_card_from_id(*id)
+                        .spawn_as_entity(commands)
+                        .expect("Failed to Spawn a Card.");
+                    card_zones.replace_in_hand(old, new);
+                    commands.remove(old);
+                }
+            }
+        }
+
+        CardChange::Scry(count) => card_zones.start_scry(count.max(0) as usize),
+    });
+}
+
+// Shows the scried cards over the board until the player is done with them. Clicking a card
+// puts it on top of the deck and right clicking discards it.
+#[system]
+#[read_component(Card)]
+#[read_component(Cost)]
+#[read_component(Keywords)]
+#[read_component(Sprite)]
+pub fn render_scry(
+    ecs: &SubWorld,
+    #[resource] card_zones: &CardZones,
+    #[resource] layout: &Layout,
+    #[resource] textures: &mut TextureAssets,
+) {
+    if card_zones.scrying.is_empty() {
+        return;
+    }
+
+    draw_rectangle(
+        0.,
+        0.,
+        layout.screen.x,
+        layout.screen.y,
+        Color::new(0.0, 0.0, 0.0, 0.6),
+    );
+    draw_text(
+        "Scry: click to put on top, right click to discard, confirm when done",
+        layout.grid_area.x,
+        layout.screen.y * 0.2,
+        28.0 * layout.scale,
+        WHITE,
+    );
+
+    layout_scry(layout, card_zones.scrying.len())
+        .into_iter()
+        .zip(card_zones.scrying.iter())
+        .enumerate()
+        .for_each(|(idx, (placement, card))| {
+            if let Ok(entry) = ecs.entry_ref(*card) {
+                render_card(
+                    layout,
+                    placement,
+                    entry
+                        .get_component::<Card>()
+                        .map(|card| card.name.clone())
+                        .unwrap_or_default(),
+                    entry
+                        .get_component::<Cost>()
+                        .map(|cost| cost.amount)
+                        .unwrap_or(0),
+                    get_keyword_text(entry.get_component::<Keywords>().ok()),
+                    textures.get_sprite(entry.get_component::<Sprite>().ok()),
+                    None,
+                    // Numbered by the order they'll be drawn in.
+                    Some(idx),
+                );
+            }
+        });
+}
+
+#[system]
+pub fn scry_input(
+    #[resource] card_zones: &mut CardZones,
+    #[resource] bindings: &InputBindings,
+    #[resource] layout: &Layout,
+) {
+    if card_zones.scrying.is_empty() {
+        return;
+    }
+
+    let mouse = Vec2::from(mouse_position());
+    let placements = layout_scry(layout, card_zones.scrying.len());
+    if let Some(idx) = get_card_at_point(&placements, layout.card_size, mouse) {
+        if is_mouse_button_pressed(MouseButton::Left) {
+            card_zones.scry_to_top(idx);
+        } else if is_mouse_button_pressed(MouseButton::Right) {
+            card_zones.scry_to_discard(idx);
+        }
+    }
+
+    if bindings.is_pressed(InputAction::Confirm) {
+        card_zones.finish_scry();
+    }
+}
+
+// Applies the hand changes held back since the player last declared, once the new hand is in.
+#[system]
+#[read_component(Message)]
+#[read_component(PlayCard)]
+#[allow(clippy::too_many_arguments)]
+pub fn apply_pending_card_changes(
+    ecs: &SubWorld,
+    commands: &mut CommandBuffer,
+    #[resource] card_zones: &mut CardZones,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] db: &mut CardDB,
+    #[resource] cursor: &mut PlayerCursor,
+    #[resource] rng: &mut StdRng,
+) {
+    if turn_tracker.turn_state != TurnState::DeclarePhase || card_zones.pending_changes.is_empty() {
+        return;
+    }
+
+    // Discards can shift which card sits at each hand index.
+    cursor.selected_card = None;
+    cursor.targeting = false;
+
+    let pending = std::mem::take(&mut card_zones.pending_changes);
+    apply_card_changes(
+        &pending,
+        turn_tracker.turn_state,
+        &get_queued_cards(ecs),
+        commands,
+        card_zones,
+        db,
+        rng,
+    );
+}
+
+// Cards the player has declared but that haven't been played yet.
+fn get_queued_cards(ecs: &SubWorld) -> Vec<Entity> {
+    <(&Message, &PlayCard)>::query()
+        .iter(ecs)
+        .map(|(_, play)| play.card)
+        .collect()
+}
+
+// Discards the old hand and draws a new one between rounds.
+#[system]
+#[read_component(Keywords)]
+pub fn cycle_hand(
+    ecs: &SubWorld,
+    #[resource] card_zones: &mut CardZones,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] cursor: &mut PlayerCursor,
+    #[resource] rng: &mut StdRng,
+) {
+    let round_ended = turn_tracker
+        .events()
+        .iter()
+        .any(|event| matches!(event, TurnEvent::RoundEnded(_)));
+    if !round_ended {
+        return;
+    }
+
+    card_zones.end_round(|card| get_card_keywords(ecs, card));
+    card_zones.draw(HAND_SIZE.saturating_sub(card_zones.hand.len()), rng);
+
+    // Hand indices are about to mean different cards.
+    cursor.selected_card = None;
+    cursor.targeting = false;
+    cursor.drag_origin = None;
+}
+
+fn get_keyword_text(keywords: Option<&Keywords>) -> String {
+    keywords
+        .map(|keywords| {
+            keywords
+                .list
+                .iter()
+                .map(|keyword| keyword.label())
+                .collect::<Vec<&str>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+fn get_card_keywords(ecs: &SubWorld, card: &Entity) -> Vec<Keyword> {
+    ecs.entry_ref(*card)
+        .ok()
+        .and_then(|entry| {
+            entry
+                .get_component::<Keywords>()
+                .ok()
+                .map(|keywords| keywords.list.clone())
+        })
+        .unwrap_or_default()
+}
+
+// The player entity, if it's the player's turn to declare and they haven't confirmed yet.
+fn get_declaring_player(ecs: &SubWorld, turn_tracker: &TurnTracker) -> Option<Entity> {
+    if turn_tracker.turn_state != TurnState::DeclarePhase {
+        return None;
+    }
+
+    let entity = turn_tracker.get_current_combatant()?.entity;
+    ecs.entry_ref(entity).ok()?.get_component::<Player>().ok()?;
+
+    let finished = <(&Message, &Source, &ActionDeclarationFinished)>::query()
+        .iter(ecs)
+        .any(|(_, src, _)| src.entity == entity);
+
+    match finished {
+        true => None,
+        false => Some(entity),
+    }
+}
+
+fn get_taunters(ecs: &SubWorld) -> Vec<Coordinate> {
+    <(&Taunt, &Coordinate)>::query()
+        .iter(ecs)
+        .map(|(_, coord)| *coord)
+        .collect()
+}
+
+// Energy committed to queued card plays, and the player's total energy for the round.
+fn get_player_energy(ecs: &SubWorld, player: Entity) -> (i32, i32) {
+    let used = <(&Message, &Source, &PlayCard)>::query()
+        .iter(ecs)
+        .filter(|(_, src, _)| src.entity == player)
+        .map(|(_, _, play)| get_card_cost(ecs, &play.card))
+        .sum();
+
+    let available = ecs
+        .entry_ref(player)
+        .ok()
+        .and_then(|entry| {
+            entry
+                .get_component::<ActionPoints>()
+                .ok()
+                .map(|ap| ap.current)
+        })
+        .unwrap_or(0);
+
+    (used, available)
+}
+
+fn get_card_cost(ecs: &SubWorld, card: &Entity) -> i32 {
+    ecs.entry_ref(*card)
+        .ok()
+        .and_then(|entry| entry.get_component::<Cost>().ok().map(|cost| cost.amount))
+        .unwrap_or(0)
+}
+
+fn is_card_queued(ecs: &SubWorld, card: &Entity) -> bool {
+    <(&Message, &PlayCard)>::query()
+        .iter(ecs)
+        .any(|(_, play)| play.card == *card)
+}
+
+// Where each card in hand should be drawn this frame, and which card (hovered, selected or
+// being dragged) has focus.
+fn get_hand_placements(
+    ecs: &SubWorld,
+    card_zones: &CardZones,
+    cursor: &PlayerCursor,
+    layout: &Layout,
+    mouse: Vec2,
+) -> (Vec<CardPlacement>, Option<usize>) {
+    let hand_size = card_zones.hand.len();
+    let resting = layout_hand(layout, hand_size, None);
+
+    let dragging = cursor.drag_origin.and(cursor.selected_card);
+    let focused = dragging
+        .or_else(|| get_card_at_point(&resting, layout.card_size, mouse))
+        .or(cursor.selected_card)
+        .filter(|idx| *idx < hand_size);
+
+    let mut placements = layout_hand(layout, hand_size, focused);
+    placements
+        .iter_mut()
+        .zip(card_zones.hand.iter())
+        .enumerate()
+        .for_each(|(idx, (placement, card))| {
+            if dragging == Some(idx) {
+                placement.center = mouse;
+            } else if Some(idx) != focused && is_card_queued(ecs, card) {
+                placement.center.y -= QUEUED_CARD_RAISE * layout.scale;
+            }
+        });
+
+    (placements, focused)
+}
+
+fn render_button(rect: Rect, label: &str, scale: f32) {
+    let hovered = rect.contains(Vec2::from(mouse_position()));
+    let fill = match hovered {
+        true => LIGHTGRAY,
+        false => GRAY,
+    };
+
+    draw_rectangle(rect.x, rect.y, rect.w, rect.h, fill);
+    draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 3.0, BLACK);
+
+    let font_size = (28.0 * scale) as u16;
+    let measurement = measure_text(label, None, font_size, 1.0);
+    draw_text(
+        label,
+        rect.x + (rect.w - measurement.width) / 2.0,
+        rect.y + (rect.h + measurement.height) / 2.0,
+        font_size as f32,
+        BLACK,
+    );
+}
+
+fn draw_debug_hand_zone(layout: &Layout) {
     draw_line(
-        HAND_ZONE_SPAN.start,
-        HAND_ZONE_Y + CARD_MAX_ARC_DOWN,
-        HAND_ZONE_SPAN.end,
-        HAND_ZONE_Y + CARD_MAX_ARC_DOWN,
+        layout.hand_zone_start,
+        layout.hand_zone_y + layout.card_arc_down,
+        layout.hand_zone_end,
+        layout.hand_zone_y + layout.card_arc_down,
         4.0,
         BLUE,
     );
     draw_line(
-        HAND_ZONE_SPAN.start,
-        HAND_ZONE_Y,
-        HAND_ZONE_SPAN.end,
-        HAND_ZONE_Y,
+        layout.hand_zone_start,
+        layout.hand_zone_y,
+        layout.hand_zone_end,
+        layout.hand_zone_y,
         4.0,
         BLUE,
     );
 }
 
-fn calculate_card_pos(idx_in_hand: i32, total_cards_in_hand: i32) -> Vec2 {
-    let width_per_card = HAND_ZONE_WIDTH / total_cards_in_hand as f32;
+// Draws cards in their own rotated and scaled space, so everything on the card face can be laid
+// out as if the card were upright at the origin.
+struct CardCamera {
+    matrix: Mat4,
+}
 
-    let center_idx = (total_cards_in_hand - 1) as f32 / 2.0;
-    let dist_from_center_idx = (idx_in_hand as f32 - center_idx).abs();
-    let dist_factor = (dist_from_center_idx / center_idx).powi(2);
+impl CardCamera {
+    fn new(placement: CardPlacement, card_size: Vec2) -> Self {
+        let screen = Mat4::orthographic_rh_gl(0., screen_width(), screen_height(), 0., -1., 1.);
+        let matrix = screen
+            * Mat4::from_translation(vec3(placement.center.x, placement.center.y, 0.))
+            * Mat4::from_rotation_z(placement.rotation)
+            * Mat4::from_scale(vec3(placement.scale, placement.scale, 1.))
+            * Mat4::from_translation(vec3(-card_size.x * 0.5, -card_size.y * 0.5, 0.));
 
-    if idx_in_hand == 0 {
-        eprintln!("{dist_factor:?}");
+        Self { matrix }
     }
+}
 
-    Vec2::new(
-        HAND_ZONE_SPAN.start + (width_per_card * (idx_in_hand as f32 + 0.5)),
-        HAND_ZONE_Y + (CARD_MAX_ARC_DOWN * dist_factor),
-    )
+impl Camera for CardCamera {
+    fn matrix(&self) -> Mat4 {
+        self.matrix
+    }
+
+    fn depth_enabled(&self) -> bool {
+        false
+    }
+
+    fn render_pass(&self) -> Option<miniquad::RenderPass> {
+        None
+    }
+
+    fn viewport(&self) -> Option<(i32, i32, i32, i32)> {
+        None
+    }
 }
 
-fn render_card(pos: Vec2, card_name: String, cost: i32, card_text: String) {
-    draw_rectangle(pos.x, pos.y, CARD_WIDTH, CARD_HEIGHT, BLACK);
-    let border_width = 10.0;
-    let inner_tl = pos + Vec2::new(border_width, border_width);
-    let inner_dimensions =
-        Vec2::new(CARD_WIDTH, CARD_HEIGHT) - (Vec2::new(border_width, border_width) * 2.0);
+#[allow(clippy::too_many_arguments)]
+fn render_card(
+    layout: &Layout,
+    placement: CardPlacement,
+    card_name: String,
+    cost: i32,
+    card_text: String,
+    art: Option<Texture2D>,
+    highlight: Option<Color>,
+    queued_order: Option<usize>,
+) {
+    set_camera(&CardCamera::new(placement, layout.card_size));
+
+    draw_rectangle(0., 0., layout.card_size.x, layout.card_size.y, BLACK);
+    let border_width = 10.0 * layout.scale;
+    let inner_tl = Vec2::new(border_width, border_width);
+    let inner_dimensions = layout.card_size - (Vec2::new(border_width, border_width) * 2.0);
     draw_rectangle(
         inner_tl.x,
         inner_tl.y,
@@ -77,6 +930,34 @@ fn render_card(pos: Vec2, card_name: String, cost: i32, card_text: String) {
         DARKPURPLE,
     );
 
+    // Art fills the middle of the card, between the title and the cost.
+    if let Some(texture) = art {
+        let art_height = inner_dimensions.y * 0.5;
+        draw_texture_in_rect(
+            texture,
+            Rect::new(
+                inner_tl.x,
+                inner_tl.y + (inner_dimensions.y - art_height) * 0.5,
+                inner_dimensions.x,
+                art_height,
+            ),
+        );
+    }
+
+    if let Some(color) = highlight {
+        draw_rectangle_lines(0., 0., layout.card_size.x, layout.card_size.y, 6.0, color);
+    }
+
+    if let Some(order) = queued_order {
+        draw_text(
+            &(order + 1).to_string(),
+            layout.card_size.x - 28.0 * layout.scale,
+            36.0 * layout.scale,
+            40.0 * layout.scale,
+            GOLD,
+        );
+    }
+
     // TODO: Figure out why this doesn't really work with longer titled cards.
     let mut font_size = 100;
     while measure_text(&card_name, None, font_size, 1.0).width >= inner_dimensions.x {
@@ -99,4 +980,15 @@ fn render_card(pos: Vec2, card_name: String, cost: i32, card_text: String) {
         font_size as f32,
         BLACK,
     );
+
+    let keyword_size = 22.0 * layout.scale;
+    draw_text(
+        &card_text,
+        inner_tl.x,
+        inner_tl.y + inner_dimensions.y - font_size as f32,
+        keyword_size,
+        GOLD,
+    );
+
+    set_default_camera();
 }
@@ -5,114 +5,535 @@ pub fn clear_bg() {
     clear_background(DARKBROWN);
 }
 
+const FLOOR_TILE: &str = "tiles/floor.png";
+
 #[system]
-pub fn draw_grid(#[resource] grid: &BattleGrid) {
+pub fn draw_grid(#[resource] grid: &BattleGrid, #[resource] textures: &mut TextureAssets) {
+    if let Some(floor) = textures.get(FLOOR_TILE) {
+        (0..grid.height).for_each(|y| {
+            (0..grid.width).for_each(|x| {
+                let tl = grid.get_cell_tl(x, y);
+                draw_texture_in_rect(floor, Rect::new(tl.x, tl.y, grid.grid_size, grid.grid_size));
+            });
+        });
+    }
+
+    grid.hazards.iter().for_each(|hazard| {
+        let tl = grid.get_cell_tl(hazard.coord.x, hazard.coord.y);
+        let rect = Rect::new(tl.x, tl.y, grid.grid_size, grid.grid_size);
+        match textures.get(hazard.kind.texture_path()) {
+            Some(texture) => draw_texture_in_rect(texture, rect),
+            None => draw_rectangle(rect.x, rect.y, rect.w, rect.h, hazard.kind.overlay_color()),
+        }
+
+        draw_text(
+            &hazard.rounds_left.to_string(),
+            rect.x + 6.0,
+            rect.y + 22.0,
+            24.0,
+            WHITE,
+        );
+    });
+
     grid.draw_from_top_left();
 }
 
 #[system(for_each)]
+#[allow(clippy::too_many_arguments)]
 pub fn draw_grid_pieces(
+    entity: &Entity,
     coords: &Coordinate,
     name: &Name,
     color: &Color,
+    sprite: Option<&Sprite>,
+    rank: Option<&HeroRank>,
+    taunt: Option<&Taunt>,
     #[resource] grid: &BattleGrid,
+    #[resource] animations: &Animations,
+    #[resource] textures: &mut TextureAssets,
 ) {
-    let tl = grid.get_cell_tl(coords.x, coords.y);
+    let tl = match animations.get_piece_screen_pos(entity, grid) {
+        Some(center) => center - Vec2::new(grid.grid_size * 0.5, grid.grid_size * 0.5),
+        None => grid.get_cell_tl(coords.x, coords.y),
+    };
 
-    draw_rectangle(
-        tl.x + 4.0,
-        tl.y + 4.0,
-        grid.grid_size - 8.0,
-        grid.grid_size - 8.0,
-        *color,
-    );
+    if let Some(texture) = textures.get_sprite(sprite) {
+        draw_texture_in_rect(
+            texture,
+            Rect::new(tl.x, tl.y, grid.grid_size, grid.grid_size),
+        );
+    } else {
+        draw_rectangle(
+            tl.x + 4.0,
+            tl.y + 4.0,
+            grid.grid_size - 8.0,
+            grid.grid_size - 8.0,
+            *color,
+        );
+
+        draw_text(
+            &name.val[0..1],
+            tl.x + (grid.grid_size * 0.5),
+            tl.y + grid.grid_size - 8.0,
+            grid.grid_size * 0.5,
+            BLACK,
+        );
+    }
+
+    // Elites and champions get a gold frame so they stand out from the rest of the party.
+    let frame = match rank {
+        Some(HeroRank::Elite) => 3.0,
+        Some(HeroRank::Champion) => 6.0,
+        _ => 0.0,
+    };
+    if frame > 0.0 {
+        draw_rectangle_lines(
+            tl.x + 2.0,
+            tl.y + 2.0,
+            grid.grid_size - 4.0,
+            grid.grid_size - 4.0,
+            frame,
+            GOLD,
+        );
+    }
+
+    if taunt.is_some() {
+        draw_text("!", tl.x + 8.0, tl.y + 26.0, 32.0, GOLD);
+    }
+}
+
+#[system]
+pub fn draw_phase_timer(#[resource] timer: &PhaseTimer, #[resource] layout: &Layout) {
+    let mut status = format!("Speed: {:?}", timer.speed);
+    if timer.fast_forward {
+        status.push_str(" >>");
+    }
+    if timer.paused {
+        status.push_str(" (Paused - N to step)");
+    }
 
     draw_text(
-        &name.val[0..1],
-        tl.x + (grid.grid_size * 0.5),
-        tl.y + grid.grid_size - 8.0,
-        48.0,
-        BLACK,
+        &status,
+        layout.status_pos.x,
+        layout.status_pos.y,
+        24.0 * layout.scale,
+        WHITE,
     );
 }
 
 #[system]
 #[read_component(Name)]
-pub fn draw_turn_tracker(ecs: &SubWorld, #[resource] turn_tracker: &TurnTracker) {
-    let origin = Vec2::new(1000., 64.);
-    draw_rectangle(origin.x, origin.y, 150.0, 200.0, GRAY);
+#[read_component(Color)]
+#[read_component(Health)]
+#[read_component(ActionPoints)]
+#[read_component(Coordinate)]
+#[read_component(Message)]
+#[read_component(Source)]
+#[read_component(ActionDeclarationFinished)]
+#[read_component(Sprite)]
+pub fn draw_turn_tracker(
+    ecs: &SubWorld,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] grid: &BattleGrid,
+    #[resource] screen_layout: &Layout,
+    #[resource] textures: &mut TextureAssets,
+) {
+    let layout = layout_turn_tracker(
+        screen_layout.tracker,
+        screen_layout.scale,
+        turn_tracker.combatants.len(),
+        turn_tracker.current_combatant,
+    );
 
+    draw_rectangle(
+        layout.header.x,
+        layout.header.y,
+        layout.header.w,
+        layout.header.h,
+        DARKGRAY,
+    );
     draw_text(
-        &format!("{:?}", turn_tracker.turn_state),
-        origin.x,
-        origin.y + 32.0,
-        32.0,
-        BLACK,
+        &format!(
+            "Round {} - {:?}",
+            turn_tracker.round, turn_tracker.turn_state
+        ),
+        layout.header.x + 8.0,
+        layout.header.y + 28.0 * screen_layout.scale,
+        24.0 * screen_layout.scale,
+        WHITE,
     );
 
-    turn_tracker
-        .combatants
-        .iter()
-        .enumerate()
-        .for_each(|(idx, combatant)| {
-            let name = match ecs
-                .entry_ref(combatant.entity)
-                .unwrap()
-                .get_component::<Name>()
-            {
-                Ok(name) => format!("[{}] {}", combatant.init, name.val),
+    let mouse = Vec2::from(mouse_position());
+    let hovered_cell = grid.get_cell_at_screen_pos(mouse);
+    let mut declared_query = <(&Message, &Source, &ActionDeclarationFinished)>::query();
 
-                _ => "???".to_string(),
+    layout
+        .visible
+        .clone()
+        .zip(layout.rows.iter())
+        .for_each(|(idx, row)| {
+            let combatant = turn_tracker.combatants[idx];
+            let entry = match ecs.entry_ref(combatant.entity) {
+                Ok(entry) => entry,
+                Err(_) => return,
             };
 
-            let display_text = match idx == turn_tracker.current_combatant {
-                true => format!("> {name}"),
+            let name = entry
+                .get_component::<Name>()
+                .map(|name| name.val.clone())
+                .unwrap_or_else(|_| "???".to_string());
+            let color = entry.get_component::<Color>().copied().unwrap_or(WHITE);
+            let coord = entry.get_component::<Coordinate>().ok().copied();
 
-                false => format!("  {name}"),
-            };
+            let is_current = turn_tracker.is_current_combatant(&combatant.entity);
+            let row_hovered = row.contains(mouse);
+            let piece_hovered = coord.is_some() && coord == hovered_cell;
+
+            draw_rectangle(row.x, row.y, row.w, row.h, GRAY);
+            if is_current {
+                draw_rectangle_lines(row.x, row.y, row.w, row.h, 4.0, GOLD);
+            } else if row_hovered || piece_hovered {
+                draw_rectangle_lines(row.x, row.y, row.w, row.h, 2.0, YELLOW);
+            }
+
+            // Portrait
+            let portrait = row.h - 8.0;
+            match textures.get_sprite(entry.get_component::<Sprite>().ok()) {
+                Some(texture) => draw_texture_in_rect(
+                    texture,
+                    Rect::new(row.x + 4.0, row.y + 4.0, portrait, portrait),
+                ),
+
+                None => {
+                    draw_rectangle(row.x + 4.0, row.y + 4.0, portrait, portrait, color);
+                    draw_text(
+                        &name[0..1],
+                        row.x + 4.0 + portrait * 0.25,
+                        row.y + 4.0 + portrait * 0.8,
+                        portrait,
+                        BLACK,
+                    );
+                }
+            }
+
+            let text_x = row.x + portrait + 12.0;
+            let font_size = (row.h * 0.45).min(22.0);
             draw_text(
-                &display_text,
-                origin.x,
-                60.0 + origin.y + idx as f32 * 32.0,
-                24.,
+                &format!("[{}] {}", combatant.init, name),
+                text_x,
+                row.y + font_size,
+                font_size,
                 BLACK,
             );
+
+            if let Ok(health) = entry.get_component::<Health>() {
+                let bar_width = row.w - portrait - 60.0;
+                let bar_height = (row.h * 0.2).max(4.0);
+                let bar_y = row.bottom() - bar_height - 4.0;
+                let filled = health.current.max(0) as f32 / health.max.max(1) as f32;
+
+                draw_rectangle(text_x, bar_y, bar_width, bar_height, MAROON);
+                draw_rectangle(text_x, bar_y, bar_width * filled, bar_height, RED);
+                draw_text(
+                    &format!("{}/{}", health.current, health.max),
+                    text_x + bar_width + 4.0,
+                    bar_y + bar_height,
+                    16.0,
+                    BLACK,
+                );
+            }
+
+            // Status icons: remaining action points, and whether they've declared this round.
+            let icon_size = (row.h * 0.18).max(3.0);
+            if let Ok(ap) = entry.get_component::<ActionPoints>() {
+                (0..ap.max).for_each(|pip| {
+                    let pip_color = match pip < ap.current {
+                        true => SKYBLUE,
+                        false => DARKGRAY,
+                    };
+                    draw_circle(
+                        row.right() - 8.0 - pip as f32 * icon_size * 2.5,
+                        row.y + icon_size + 4.0,
+                        icon_size,
+                        pip_color,
+                    );
+                });
+            }
+
+            if declared_query
+                .iter(ecs)
+                .any(|(_, src, _)| src.entity == combatant.entity)
+            {
+                draw_circle(
+                    row.right() - 8.0,
+                    row.bottom() - icon_size - 4.0,
+                    icon_size,
+                    GREEN,
+                );
+            }
+
+            // Tie the bar to the board: hovering a row highlights the piece it belongs to.
+            if let (true, Some(coord)) = (row_hovered, coord) {
+                let tl = grid.get_cell_tl(coord.x, coord.y);
+                draw_rectangle_lines(tl.x, tl.y, grid.grid_size, grid.grid_size, 6.0, YELLOW);
+            }
         });
 }
 
-#[system(for_each)]
+#[system]
+#[read_component(Message)]
+#[read_component(Source)]
+#[read_component(Move)]
 #[read_component(Coordinate)]
 #[read_component(Color)]
-pub fn draw_declared_moves(
-    ecs: &mut SubWorld,
-    _msg: &Message,
-    src: &Source,
-    mv: &Move,
-    #[resource] grid: &BattleGrid,
-) {
-    let mut entity_query = <(Entity, &Color, &Coordinate)>::query();
-
-    entity_query
+pub fn draw_declared_moves(ecs: &SubWorld, #[resource] grid: &BattleGrid) {
+    let mut move_query = <(&Message, &Source, &Move)>::query();
+    let moves = move_query
         .iter(ecs)
-        .filter(|(entity, _, _)| **entity == src.entity)
-        .for_each(|(_, color, coord)| {
-            let mut current_coord = *coord;
-            mv.dirs.iter().for_each(|dir| {
-                let new_coord = current_coord + Coordinate::from(*dir);
+        .filter_map(|(_, src, mv)| {
+            let entry = ecs.entry_ref(src.entity).ok()?;
+            let coord = *entry.get_component::<Coordinate>().ok()?;
+            let color = entry.get_component::<Color>().copied().unwrap_or(WHITE);
+
+            Some((coord, color, mv))
+        })
+        .collect::<Vec<(Coordinate, Color, &Move)>>();
+
+    let lane_count = moves.len();
+    moves
+        .iter()
+        .enumerate()
+        .for_each(|(lane, (coord, color, mv))| {
+            let destination = get_truncated_destination(*coord, &mv.dirs, grid);
+            let layout = layout_move_path(grid, *coord, &mv.dirs, destination, lane, lane_count);
 
-                let line_start = grid.get_cell_center(current_coord.x, current_coord.y);
-                let line_end = grid.get_cell_center(new_coord.x, new_coord.y);
+            layout.segments.iter().for_each(|segment| {
+                let segment_color = match segment.blocked {
+                    true => Color::new(color.r, color.g, color.b, 0.35),
+                    false => *color,
+                };
 
                 draw_line(
-                    line_start.x,
-                    line_start.y,
-                    line_end.x,
-                    line_end.y,
+                    segment.start.x,
+                    segment.start.y,
+                    segment.end.x,
+                    segment.end.y,
+                    4.0,
+                    segment_color,
+                );
+                draw_triangle(
+                    segment.arrow.0,
+                    segment.arrow.1,
+                    segment.arrow.2,
+                    segment_color,
+                );
+
+                let label_pos = segment.midpoint();
+                draw_text(
+                    &segment.step.to_string(),
+                    label_pos.x - 4.0,
+                    label_pos.y - 6.0,
+                    20.0,
+                    segment_color,
+                );
+            });
+
+            if let Some(marker) = layout.blocked_marker {
+                let size = 10.0;
+                draw_line(
+                    marker.x - size,
+                    marker.y - size,
+                    marker.x + size,
+                    marker.y + size,
+                    4.0,
+                    *color,
+                );
+                draw_line(
+                    marker.x - size,
+                    marker.y + size,
+                    marker.x + size,
+                    marker.y - size,
                     4.0,
                     *color,
                 );
+            }
+        });
+}
+
+#[system]
+#[read_component(Message)]
+#[read_component(Source)]
+#[read_component(Attack)]
+#[read_component(Color)]
+#[read_component(Name)]
+pub fn draw_declared_attacks(ecs: &SubWorld, #[resource] grid: &BattleGrid) {
+    let mut attack_query = <(&Message, &Source, &Attack)>::query();
+    let previews = build_attack_previews(
+        attack_query
+            .iter(ecs)
+            .map(|(_, src, attack)| (src.entity, attack)),
+    );
+
+    previews.iter().for_each(|preview| {
+        let tl = grid.get_cell_tl(preview.coord.x, preview.coord.y);
+
+        preview
+            .sources
+            .iter()
+            .enumerate()
+            .for_each(|(idx, source)| {
+                let (color, initial) = match ecs.entry_ref(*source) {
+                    Ok(entry) => (
+                        entry.get_component::<Color>().copied().unwrap_or(WHITE),
+                        entry
+                            .get_component::<Name>()
+                            .map(|name| name.val[0..1].to_string())
+                            .unwrap_or_else(|_| "?".to_string()),
+                    ),
+
+                    Err(_) => (WHITE, "?".to_string()),
+                };
+
+                draw_rectangle(
+                    tl.x,
+                    tl.y,
+                    grid.grid_size,
+                    grid.grid_size,
+                    Color::new(color.r, color.g, color.b, 0.35),
+                );
+                draw_rectangle_lines(tl.x, tl.y, grid.grid_size, grid.grid_size, 4.0, color);
 
-                current_coord = new_coord;
+                draw_text(
+                    &initial,
+                    tl.x + 4.0 + idx as f32 * 20.0,
+                    tl.y + 24.0,
+                    24.0,
+                    color,
+                );
             });
+
+        draw_text(
+            &format!("-{}", preview.total_damage),
+            tl.x + grid.grid_size - 36.0,
+            tl.y + 24.0,
+            28.0,
+            RED,
+        );
+    });
+}
+
+// Shows each declared shove or pull as an arrow on the cells it acts on, pointing the way the
+// piece there will be moved.
+#[system]
+#[read_component(Message)]
+#[read_component(Source)]
+#[read_component(ForcedMove)]
+#[read_component(Color)]
+pub fn draw_declared_forced_moves(ecs: &SubWorld, #[resource] grid: &BattleGrid) {
+    let mut forced_query = <(&Message, &Source, &ForcedMove)>::query();
+    forced_query.iter(ecs).for_each(|(_, src, forced)| {
+        let color = ecs
+            .entry_ref(src.entity)
+            .ok()
+            .and_then(|entry| entry.get_component::<Color>().ok().copied())
+            .unwrap_or(WHITE);
+
+        forced.targets.iter().for_each(|target| {
+            let dir = match get_direction_towards(forced.origin, *target) {
+                Some(dir) if forced.distance < 0 => dir.reverse(),
+                Some(dir) => dir,
+                None => return,
+            };
+
+            let tl = grid.get_cell_tl(target.x, target.y);
+            draw_rectangle_lines(tl.x, tl.y, grid.grid_size, grid.grid_size, 4.0, color);
+
+            let step = Coordinate::from(dir);
+            let forward = Vec2::new(step.x as f32, step.y as f32);
+            let side = forward.perp();
+            let center = grid.get_cell_center(target.x, target.y);
+            let start = center - forward * grid.grid_size * 0.3;
+            let tip = center + forward * grid.grid_size * 0.3;
+            let base = tip - forward * 14.0;
+
+            draw_line(start.x, start.y, base.x, base.y, 4.0, color);
+            draw_triangle(tip, base + side * 8.0, base - side * 8.0, color);
+            draw_text(
+                &forced.distance.abs().to_string(),
+                tl.x + grid.grid_size - 20.0,
+                tl.y + grid.grid_size - 6.0,
+                24.0,
+                color,
+            );
         });
+    });
+}
+
+// Shows the cells a declared hazard will cover, fainter than the hazards already on the grid.
+// Nothing is left behind off the grid, so the dragon's cell is skipped.
+#[system]
+#[read_component(Message)]
+#[read_component(PlaceHazards)]
+pub fn draw_declared_hazards(ecs: &SubWorld, #[resource] grid: &BattleGrid) {
+    let mut hazard_query = <(&Message, &PlaceHazards)>::query();
+    hazard_query.iter(ecs).for_each(|(_, place)| {
+        place
+            .targets
+            .iter()
+            .filter(|target| grid.is_cell_in_bounds(**target))
+            .for_each(|target| {
+                let tl = grid.get_cell_tl(target.x, target.y);
+
+                place.hazards.iter().for_each(|(kind, rounds)| {
+                    let color = kind.overlay_color();
+                    draw_rectangle(
+                        tl.x,
+                        tl.y,
+                        grid.grid_size,
+                        grid.grid_size,
+                        Color::new(color.r, color.g, color.b, color.a * 0.5),
+                    );
+                    draw_rectangle_lines(
+                        tl.x,
+                        tl.y,
+                        grid.grid_size,
+                        grid.grid_size,
+                        2.0,
+                        Color::new(color.r, color.g, color.b, 1.0),
+                    );
+                    draw_text(
+                        &rounds.to_string(),
+                        tl.x + 6.0,
+                        tl.y + grid.grid_size - 6.0,
+                        24.0,
+                        WHITE,
+                    );
+                });
+            });
+    });
+}
+
+#[system]
+pub fn draw_hit_effects(#[resource] animations: &Animations, #[resource] grid: &BattleGrid) {
+    animations.hits.iter().for_each(|hit| {
+        let progress = hit.progress();
+        let tl = grid.get_cell_tl(hit.coord.x, hit.coord.y);
+
+        draw_rectangle(
+            tl.x,
+            tl.y,
+            grid.grid_size,
+            grid.grid_size,
+            Color::new(1.0, 1.0, 1.0, 0.8 * (1.0 - progress)),
+        );
+
+        let center = grid.get_cell_center(hit.coord.x, hit.coord.y);
+        draw_text(
+            &format!("-{}", hit.amount),
+            center.x - 12.0,
+            center.y - grid.grid_size * 0.5 * progress,
+            40.0,
+            Color::new(1.0, 0.1, 0.1, 1.0 - progress),
+        );
+    });
 }
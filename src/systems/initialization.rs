@@ -1,29 +1,140 @@
 use crate::prelude::*;
 
+// Hands a fresh run its starting relic, then brings the run's relics into the fight.
+#[system]
+pub fn equip_relics(
+    #[resource] run: &mut Run,
+    #[resource] db: &mut RelicDB,
+    #[resource] relics: &mut Relics,
+    #[resource] rng: &mut StdRng,
+) {
+    if run.relics.is_empty() {
+        if let Some(id) = db.get_relic_ids().choose(rng) {
+            run.relics.push(*id);
+        }
+    }
+
+    relics.list = run
+        .relics
+        .iter()
+        .filter_map(|id| match db.get_relic_from_id(*id).to_relic() {
+            Ok(relic) => Some(relic),
+            Err(err) => {
+                eprintln!("Skipping relic {id}: {err}");
+                None
+            }
+        })
+        .collect();
+}
+
+// The player's deck, listed by card id.
+const STARTER_DECK: [i32; 12] = [1, 1, 2, 2, 3, 3, 4, 5, 6, 8, 9, 10];
+
 #[system]
 pub fn build_starter_deck(
     commands: &mut CommandBuffer,
     #[resource] db: &mut CardDB,
     #[resource] card_zones: &mut CardZones,
+    #[resource] rng: &mut StdRng,
 ) {
-    let fire_breath_data = db.get_card_from_id(1);
-    let first_card = fire_breath_data
-        .spawn_as_entity(commands)
-        .expect("Failed to Spawn a Card.");
-
-    let claw_data = db.get_card_from_id(2);
-    let second_card = claw_data
-        .spawn_as_entity(commands)
-        .expect("Failed to Spawn a Card.");
-
-    let block_data = db.get_card_from_id(3);
-    let third_card = block_data
-        .spawn_as_entity(commands)
-        .expect("Failed to Spawn a Card.");
-
-    card_zones
-        .hand
-        .extend([first_card, second_card, third_card]);
+    let cards = STARTER_DECK
+        .iter()
+        .map(|id| {
+            let data = db.get_card_from_id(*id);
+            let card = data
+                .spawn_as_entity(commands)
+                .expect("Failed to Spawn a Card.");
+
+            (card, data.get_keywords().unwrap_or_default())
+        })
+        .collect::<Vec<(Entity, Vec<Keyword>)>>();
+
+    card_zones.build_deck(&cards, rng);
+    card_zones.draw(HAND_SIZE, rng);
+}
+
+#[system]
+pub fn spawn_player(commands: &mut CommandBuffer, #[resource] run: &Run) {
+    let mut health = Health::new(30);
+    if let Some(saved) = run.player_health {
+        health.current = saved.min(health.max);
+    }
+
+    commands.push((
+        Player,
+        Initiative {
+            init_mod: 0,
+            priority: 100,
+        },
+        Name {
+            val: "You".to_string(),
+        },
+        DARKGREEN,
+        health,
+        ActionPoints::new(3),
+        Sprite {
+            path: "dragon.png".to_string(),
+        },
+    ));
+}
+
+#[system]
+pub fn spawn_heroes(
+    commands: &mut CommandBuffer,
+    #[resource] db: &mut HeroDB,
+    #[resource] grid: &BattleGrid,
+    #[resource] difficulty: &Difficulty,
+    #[resource] rng: &mut StdRng,
+) {
+    // The final raid spends half its budget on the champion's escort.
+    let mut party = Vec::new();
+    let mut budget = difficulty.budget;
+    if difficulty.champion {
+        if let Some(champion) = db.get_hero_ids_of_rank(HeroRank::Champion).choose(rng) {
+            party.push(*champion);
+            budget /= 2;
+        }
+    }
+
+    let pool = db.get_hero_pool(difficulty.max_rank);
+    party.extend(generate_party(
+        &pool,
+        budget,
+        MAX_PARTY_SIZE - party.len(),
+        rng,
+    ));
+    let positions = get_party_positions(grid, party.len(), rng);
+
+    party.iter().zip(positions).for_each(|(id, coord)| {
+        db.get_hero_from_id(*id)
+            .scaled(difficulty)
+            .spawn_as_entity(commands, coord)
+            .expect("Failed to Spawn a Hero.");
+    });
+}
+
+// Rocks go in the empty middle rows, between the heroes and the dragon.
+#[system]
+pub fn spawn_obstacles(
+    commands: &mut CommandBuffer,
+    #[resource] grid: &BattleGrid,
+    #[resource] rng: &mut StdRng,
+) {
+    let cells = (2..grid.height - 1)
+        .flat_map(|y| (0..grid.width).map(move |x| Coordinate { x, y }))
+        .collect::<Vec<Coordinate>>();
+
+    cells.choose_multiple(rng, 2).for_each(|coord| {
+        commands.push((
+            Obstacle,
+            Name {
+                val: "Rock".to_string(),
+            },
+            *coord,
+            GRAY,
+            TileStatus::Obstacle,
+        ));
+    });
 }
 
 #[system]
@@ -0,0 +1,274 @@
+use crate::prelude::*;
+
+#[system]
+pub fn draw_menu(
+    #[resource] game_state: &GameState,
+    #[resource] menu: &MenuState,
+    #[resource] run: &Run,
+    #[resource] audio: &AudioManager,
+    #[resource] timer: &PhaseTimer,
+    #[resource] layout: &Layout,
+) {
+    // Menus over the board dim it rather than hiding it.
+    if *game_state != GameState::TitleScreen && *game_state != GameState::Settings {
+        draw_rectangle(
+            0.,
+            0.,
+            layout.screen.x,
+            layout.screen.y,
+            Color::new(0.0, 0.0, 0.0, 0.6),
+        );
+    }
+
+    let title = get_menu_title(*game_state);
+    let title_size = 72.0 * layout.scale;
+    let measurement = measure_text(title, None, title_size as u16, 1.0);
+    draw_text(
+        title,
+        (layout.screen.x - measurement.width) * 0.5,
+        layout.screen.y * 0.25,
+        title_size,
+        WHITE,
+    );
+
+    let items = get_menu_items(*game_state, Run::has_save(SAVE_PATH));
+    let mouse = Vec2::from(mouse_position());
+
+    layout_menu(layout, items.len())
+        .iter()
+        .zip(items.iter())
+        .enumerate()
+        .for_each(|(idx, (rect, item))| {
+            let fill = match menu.selected == idx || rect.contains(mouse) {
+                true => LIGHTGRAY,
+                false => GRAY,
+            };
+            draw_rectangle(rect.x, rect.y, rect.w, rect.h, fill);
+            if menu.selected == idx {
+                draw_rectangle_lines(rect.x, rect.y, rect.w, rect.h, 4.0, GOLD);
+            }
+
+            let label = get_menu_label(*item, run, &audio.settings, timer);
+            let font_size = 32.0 * layout.scale;
+            let measurement = measure_text(&label, None, font_size as u16, 1.0);
+            draw_text(
+                &label,
+                rect.x + (rect.w - measurement.width) * 0.5,
+                rect.y + (rect.h + measurement.height) * 0.5,
+                font_size,
+                BLACK,
+            );
+        });
+}
+
+fn get_menu_label(
+    item: MenuItem,
+    run: &Run,
+    settings: &AudioSettings,
+    timer: &PhaseTimer,
+) -> String {
+    match item {
+        MenuItem::NewRun => "New Run".to_string(),
+        MenuItem::Continue => "Continue".to_string(),
+        MenuItem::Seed => format!("< Seed: {} >", run.seed),
+        MenuItem::Difficulty => format!("< Difficulty: {} >", run.difficulty),
+        MenuItem::NextRaid => format!("Next Raid ({})", run.floor + 1),
+        MenuItem::Settings => "Settings".to_string(),
+        MenuItem::Resume => "Resume".to_string(),
+        MenuItem::SaveAndQuit => "Save and Quit".to_string(),
+        MenuItem::QuitToTitle => "Title Screen".to_string(),
+        MenuItem::Quit => "Quit Game".to_string(),
+        MenuItem::MasterVolume => {
+            format!("< Volume: {:.0}% >", settings.master * 100.0)
+        }
+        MenuItem::Mute => match settings.muted {
+            true => "Sound: Off".to_string(),
+            false => "Sound: On".to_string(),
+        },
+        MenuItem::Speed => format!("< Speed: {:?} >", timer.speed),
+        MenuItem::Back => "Back".to_string(),
+    }
+}
+
+#[system]
+#[read_component(Player)]
+#[read_component(Health)]
+#[allow(clippy::too_many_arguments)]
+pub fn menu_input(
+    ecs: &SubWorld,
+    #[resource] game_state: &mut GameState,
+    #[resource] menu: &mut MenuState,
+    #[resource] run: &mut Run,
+    #[resource] audio: &mut AudioManager,
+    #[resource] timer: &mut PhaseTimer,
+    #[resource] bindings: &InputBindings,
+    #[resource] layout: &Layout,
+) {
+    let items = get_menu_items(*game_state, Run::has_save(SAVE_PATH));
+
+    if bindings.is_pressed(InputAction::Cancel) {
+        match *game_state {
+            GameState::Paused => menu.open(game_state, GameState::Combat),
+            GameState::Settings => menu.open(game_state, menu.settings_return),
+            _ => {}
+        }
+        return;
+    }
+
+    [(InputAction::CursorUp, -1), (InputAction::CursorDown, 1)]
+        .iter()
+        .filter(|(action, _)| bindings.is_pressed(*action))
+        .for_each(|(_, offset)| menu.move_selection(*offset, items.len()));
+
+    let mouse = Vec2::from(mouse_position());
+    let clicked = is_mouse_button_pressed(MouseButton::Left);
+    let clicked_item = layout_menu(layout, items.len())
+        .iter()
+        .position(|rect| rect.contains(mouse));
+    if let (true, Some(idx)) = (clicked, clicked_item) {
+        menu.selected = idx;
+    }
+
+    let item = match items.get(menu.selected) {
+        Some(item) => *item,
+        None => return,
+    };
+
+    let adjust = [(InputAction::CursorLeft, -1), (InputAction::CursorRight, 1)]
+        .iter()
+        .filter(|(action, _)| bindings.is_pressed(*action))
+        .map(|(_, offset)| *offset)
+        .sum::<i32>();
+    if adjust != 0 {
+        match item {
+            MenuItem::Seed => run.seed = run.seed.saturating_add_signed(adjust as i64),
+            MenuItem::Difficulty => run.difficulty = adjust_difficulty(run.difficulty, adjust),
+            MenuItem::MasterVolume => {
+                let mut settings = audio.settings;
+                settings.change_master(adjust);
+                audio.update_settings(settings);
+            }
+            MenuItem::Speed => timer.speed = cycle_speed(timer.speed, adjust),
+            _ => {}
+        }
+    }
+
+    let confirmed =
+        bindings.is_pressed(InputAction::Confirm) || (clicked && clicked_item.is_some());
+    if !confirmed {
+        return;
+    }
+
+    match item {
+        MenuItem::NewRun => {
+            // A finished run starts the next one on a fresh seed; the title screen keeps the
+            // seed the player picked.
+            if let GameState::GameOver { .. } = *game_state {
+                run.seed = Run::random_seed();
+            }
+            *run = Run {
+                difficulty: run.difficulty,
+                ..Run::new(run.seed)
+            };
+            Run::delete_save(SAVE_PATH);
+            menu.open(game_state, GameState::Initialization);
+        }
+
+        MenuItem::Continue => {
+            if let Some(saved) = Run::load(SAVE_PATH) {
+                *run = saved;
+                menu.open(game_state, GameState::Initialization);
+            }
+        }
+
+        MenuItem::Seed => run.seed = Run::random_seed(),
+        MenuItem::Difficulty => {
+            run.difficulty = (run.difficulty + 1) % (MAX_DIFFICULTY + 1);
+        }
+        // The victory already saved the run with the next floor, so this just sets it up.
+        MenuItem::NextRaid => menu.open(game_state, GameState::Initialization),
+        MenuItem::Settings => menu.open(game_state, GameState::Settings),
+        MenuItem::Resume => menu.open(game_state, GameState::Combat),
+
+        MenuItem::SaveAndQuit => {
+            run.player_health = <(&Player, &Health)>::query()
+                .iter(ecs)
+                .map(|(_, health)| health.current)
+                .next();
+            run.save(SAVE_PATH);
+            menu.open(game_state, GameState::TitleScreen);
+        }
+
+        MenuItem::QuitToTitle => menu.open(game_state, GameState::TitleScreen),
+        MenuItem::Quit => *game_state = GameState::Quit,
+
+        MenuItem::MasterVolume => {}
+        MenuItem::Mute => {
+            let mut settings = audio.settings;
+            settings.muted = !settings.muted;
+            audio.update_settings(settings);
+        }
+        MenuItem::Speed => timer.speed = cycle_speed(timer.speed, 1),
+        MenuItem::Back => menu.open(game_state, menu.settings_return),
+    }
+}
+
+// Escape cancels a card selection first; only an idle cursor opens the pause menu.
+#[system]
+pub fn open_pause_menu(
+    #[resource] game_state: &mut GameState,
+    #[resource] menu: &mut MenuState,
+    #[resource] cursor: &PlayerCursor,
+    #[resource] bindings: &InputBindings,
+) {
+    if bindings.is_pressed(InputAction::Cancel)
+        && cursor.selected_card.is_none()
+        && !cursor.targeting
+    {
+        menu.open(game_state, GameState::Paused);
+    }
+}
+
+#[system]
+#[read_component(Player)]
+#[read_component(Enemy)]
+#[read_component(Health)]
+pub fn check_combat_over(
+    ecs: &SubWorld,
+    #[resource] game_state: &mut GameState,
+    #[resource] menu: &mut MenuState,
+    #[resource] run: &mut Run,
+    #[resource] difficulty: &Difficulty,
+    #[resource] animations: &Animations,
+) {
+    // Let the final blow play out first.
+    if animations.is_busy() {
+        return;
+    }
+
+    let player_health = <(&Player, &Health)>::query()
+        .iter(ecs)
+        .map(|(_, health)| health.current)
+        .next();
+    let player_alive = player_health.is_some_and(|health| health > 0);
+    let heroes_left = <&Enemy>::query().iter(ecs).next().is_some();
+
+    let outcome = match (player_alive, heroes_left) {
+        (false, _) => Some(false),
+        (true, false) => Some(true),
+        (true, true) => None,
+    };
+
+    if let Some(victory) = outcome {
+        // Winning carries the dragon's wounds into a tougher raid; losing, or beating the
+        // champion, ends the run.
+        if victory && !difficulty.champion {
+            run.player_health = player_health;
+            run.floor += 1;
+            run.save(SAVE_PATH);
+        } else {
+            Run::delete_save(SAVE_PATH);
+        }
+        menu.open(game_state, GameState::GameOver { victory });
+    }
+}
@@ -3,26 +3,96 @@ use crate::prelude::*;
 mod card;
 mod draw;
 mod initialization;
+mod menu;
+mod relic;
 
 pub fn build_game_initialization_schedule() -> Schedule {
     Schedule::builder()
+        .add_system(initialization::equip_relics_system())
         .add_system(initialization::build_starter_deck_system())
+        .add_system(initialization::spawn_player_system())
+        .add_system(initialization::spawn_heroes_system())
+        .add_system(initialization::spawn_obstacles_system())
         .add_system(initialization::begin_combat_system())
         .build()
 }
 
+pub fn build_title_screen_schedule() -> Schedule {
+    Schedule::builder()
+        .add_thread_local(update_layout_system())
+        .add_thread_local(draw::clear_bg_system())
+        .add_thread_local(menu::draw_menu_system())
+        .add_thread_local(menu::menu_input_system())
+        .build()
+}
+
+pub fn build_pause_menu_schedule() -> Schedule {
+    Schedule::builder()
+        .add_thread_local(update_layout_system())
+        .add_thread_local(draw::clear_bg_system())
+        .add_thread_local(draw::draw_grid_system())
+        .add_thread_local(draw::draw_turn_tracker_system())
+        .add_thread_local(relic::draw_relic_bar_system())
+        .add_thread_local(draw::draw_grid_pieces_system())
+        .add_thread_local(draw::draw_declared_moves_system())
+        .add_thread_local(draw::draw_declared_hazards_system())
+        .add_thread_local(draw::draw_declared_attacks_system())
+        .add_thread_local(draw::draw_declared_forced_moves_system())
+        .add_thread_local(card::render_hand_system())
+        .add_thread_local(menu::draw_menu_system())
+        .add_thread_local(menu::menu_input_system())
+        .build()
+}
+
+pub fn build_settings_schedule() -> Schedule {
+    Schedule::builder()
+        .add_thread_local(update_layout_system())
+        .add_thread_local(draw::clear_bg_system())
+        .add_thread_local(menu::draw_menu_system())
+        .add_thread_local(menu::menu_input_system())
+        .build()
+}
+
+pub fn build_game_over_schedule() -> Schedule {
+    Schedule::builder()
+        .add_thread_local(update_layout_system())
+        .add_thread_local(draw::clear_bg_system())
+        .add_thread_local(draw::draw_grid_system())
+        .add_thread_local(draw::draw_turn_tracker_system())
+        .add_thread_local(relic::draw_relic_bar_system())
+        .add_thread_local(draw::draw_grid_pieces_system())
+        .add_thread_local(menu::draw_menu_system())
+        .add_thread_local(menu::menu_input_system())
+        .build()
+}
+
 pub fn build_start_of_round_schedule() -> Schedule {
     Schedule::builder()
+        .add_thread_local(update_layout_system())
         .add_thread_local(draw::clear_bg_system())
         .add_thread_local(draw::draw_grid_system())
         .add_thread_local(draw::draw_turn_tracker_system())
+        .add_thread_local(relic::draw_relic_bar_system())
         .add_thread_local(draw::draw_grid_pieces_system())
         .add_thread_local(draw::draw_declared_moves_system())
+        .add_thread_local(draw::draw_declared_hazards_system())
+        .add_thread_local(draw::draw_declared_attacks_system())
+        .add_thread_local(draw::draw_declared_forced_moves_system())
+        .add_thread_local(draw::draw_hit_effects_system())
+        .add_thread_local(draw::draw_phase_timer_system())
         .add_thread_local(card::render_hand_system())
+        .add_thread_local(update_audio_system())
         .flush()
+        .add_system(menu::open_pause_menu_system())
+        .add_system(relic::trigger_relics_system())
+        .add_system(trigger_hazards_system())
+        .add_system(advance_hero_phases_system())
+        .add_system(card::cycle_hand_system())
         .add_system(roll_initiative_system())
         .add_system(clear_round_messages_system())
         .add_system(update_tile_statuses_system())
+        .add_system(phase_timer_controls_system())
+        .add_system(update_animations_system())
         .flush()
         .add_system(end_turn_system())
         .build()
@@ -30,14 +100,30 @@ pub fn build_start_of_round_schedule() -> Schedule {
 
 pub fn build_declare_phase_schedule() -> Schedule {
     Schedule::builder()
+        .add_thread_local(update_layout_system())
         .add_thread_local(draw::clear_bg_system())
         .add_thread_local(draw::draw_grid_system())
         .add_thread_local(draw::draw_turn_tracker_system())
+        .add_thread_local(relic::draw_relic_bar_system())
         .add_thread_local(draw::draw_grid_pieces_system())
         .add_thread_local(draw::draw_declared_moves_system())
+        .add_thread_local(draw::draw_declared_hazards_system())
+        .add_thread_local(draw::draw_declared_attacks_system())
+        .add_thread_local(draw::draw_declared_forced_moves_system())
+        .add_thread_local(draw::draw_hit_effects_system())
+        .add_thread_local(draw::draw_phase_timer_system())
         .add_thread_local(card::render_hand_system())
+        .add_thread_local(update_audio_system())
+        .add_thread_local(card::render_declaration_ui_system())
         .flush()
+        .add_system(menu::open_pause_menu_system())
+        .add_system(relic::trigger_relics_system())
+        .add_system(advance_hero_phases_system())
+        .add_system(card::apply_pending_card_changes_system())
         .add_system(declare_ai_action_system())
+        .add_system(card::declare_player_action_system())
+        .add_system(phase_timer_controls_system())
+        .add_system(update_animations_system())
         .flush()
         .add_system(end_turn_system())
         .build()
@@ -45,93 +131,372 @@ pub fn build_declare_phase_schedule() -> Schedule {
 
 pub fn build_resolve_phase_schedule() -> Schedule {
     Schedule::builder()
+        .add_thread_local(update_layout_system())
         .add_thread_local(draw::clear_bg_system())
         .add_thread_local(draw::draw_grid_system())
         .add_thread_local(draw::draw_turn_tracker_system())
+        .add_thread_local(relic::draw_relic_bar_system())
         .add_thread_local(draw::draw_grid_pieces_system())
         .add_thread_local(draw::draw_declared_moves_system())
+        .add_thread_local(draw::draw_declared_hazards_system())
+        .add_thread_local(draw::draw_declared_attacks_system())
+        .add_thread_local(draw::draw_declared_forced_moves_system())
+        .add_thread_local(draw::draw_hit_effects_system())
+        .add_thread_local(draw::draw_phase_timer_system())
         .add_thread_local(card::render_hand_system())
+        .add_thread_local(card::render_scry_system())
+        .add_thread_local(update_audio_system())
         .flush()
+        .add_system(menu::open_pause_menu_system())
+        .add_system(relic::trigger_relics_system())
+        .add_system(card::resolve_card_plays_system())
+        .add_system(card::resolve_card_changes_system())
         .add_system(resolve_moves_system())
+        .add_system(resolve_attacks_system())
+        .add_system(resolve_forced_moves_system())
+        .add_system(resolve_hazard_placement_system())
+        .add_system(trigger_hazards_system())
+        .add_system(advance_hero_phases_system())
+        .add_system(resolve_initiative_changes_system())
+        .add_system(card::scry_input_system())
+        .add_system(phase_timer_controls_system())
+        .add_system(update_animations_system())
         .flush()
         .add_system(end_turn_system())
+        .add_system(menu::check_combat_over_system())
         .build()
 }
 
+#[system]
+fn update_layout(#[resource] layout: &mut Layout, #[resource] grid: &mut BattleGrid) {
+    let screen = Vec2::new(screen_width(), screen_height());
+
+    if screen != layout.screen {
+        *layout = Layout::new(screen);
+        layout.fit_grid(grid);
+    }
+}
+
 #[system(for_each)]
-fn roll_initiative(entity: &Entity, init: &Initiative, #[resource] turn_tracker: &mut TurnTracker) {
-    let init_roll = thread_rng().gen_range(1..=10) + init.init_mod;
-    turn_tracker.register_combatant(entity, init_roll, init.priority);
+fn roll_initiative(
+    entity: &Entity,
+    init: &Initiative,
+    #[resource] turn_tracker: &mut TurnTracker,
+    #[resource] rng: &mut StdRng,
+) {
+    let init_roll = roll_init(init.init_mod, rng);
+    turn_tracker.register_combatant(entity, init_roll, init.priority, init.init_mod);
+}
+
+fn roll_init(init_mod: i32, rng: &mut StdRng) -> i32 {
+    rng.gen_range(1..=10) + init_mod
+}
+
+// Champions change tactics as they're worn down, calling in reinforcements who join the fight
+// straight away instead of waiting for the next initiative roll.
+#[system]
+#[read_component(Coordinate)]
+#[read_component(Health)]
+#[write_component(Phases)]
+#[write_component(Abilities)]
+#[allow(clippy::too_many_arguments)]
+fn advance_hero_phases(
+    ecs: &mut SubWorld,
+    commands: &mut CommandBuffer,
+    #[resource] db: &mut HeroDB,
+    #[resource] grid: &mut BattleGrid,
+    #[resource] turn_tracker: &mut TurnTracker,
+    #[resource] difficulty: &Difficulty,
+    #[resource] rng: &mut StdRng,
+) {
+    let mut reinforcements = Vec::new();
+
+    <(&Coordinate, &Health, &mut Phases, &mut Abilities)>::query()
+        .iter_mut(ecs)
+        .filter(|(_, health, _, _)| health.current > 0)
+        .for_each(|(coord, health, phases, abilities)| {
+            let reached = get_reached_phases(&phases.list, health.current);
+            if reached <= phases.reached {
+                return;
+            }
+
+            phases.list[phases.reached..reached]
+                .iter()
+                .flat_map(|phase| phase.reinforcements.iter())
+                .for_each(|id| reinforcements.push((*id, *coord)));
+            abilities.list = phases.list[reached - 1].abilities.clone();
+            phases.reached = reached;
+        });
+
+    reinforcements.into_iter().for_each(|(id, near)| {
+        let hero = db.get_hero_from_id(id).scaled(difficulty);
+        if let Some(coord) = get_reinforcement_positions(grid, near, 1).first() {
+            let entity = hero
+                .spawn_as_entity(commands, *coord)
+                .expect("Failed to Spawn a Hero.");
+            // Claim the cell now so the next reinforcement lands somewhere else.
+            grid.set_status_at_coord(coord, hero.get_tile_status());
+
+            let init_mod = hero.get_init_mod();
+            turn_tracker.register_combatant(&entity, roll_init(init_mod, rng), 0, init_mod);
+        }
+    });
 }
 
 #[system]
-#[read_component(Player)]
 #[read_component(Message)]
 #[read_component(Source)]
 #[read_component(ActionDeclarationFinished)]
 fn end_turn(
     ecs: &SubWorld,
     #[resource] turn_tracker: &mut TurnTracker,
-    #[resource] timer: &mut f32,
+    #[resource] timer: &mut PhaseTimer,
+    #[resource] animations: &Animations,
+    #[resource] card_zones: &CardZones,
 ) {
-    let turn_entity = ecs
-        .entry_ref(turn_tracker.get_current_combatant().entity)
-        .unwrap();
+    // Let the board catch up with what was just resolved before anything else happens, and wait
+    // for the player to finish scrying.
+    if animations.is_busy() || !card_zones.scrying.is_empty() {
+        return;
+    }
 
     match turn_tracker.turn_state {
         TurnState::DeclarePhase => {
-            if let Ok(_player) = turn_entity.get_component::<Player>() {
-                if is_key_pressed(KeyCode::Space) {
-                    turn_tracker.next_turn();
-                }
-            } else {
-                let mut msg_query = <(&Message, &Source, &ActionDeclarationFinished)>::query();
-
-                if msg_query
-                    .iter(ecs)
-                    .filter(|(_, src, _)| src.entity == turn_tracker.get_current_combatant().entity)
-                    .nth(0)
-                    .is_some()
-                {
+            // Nobody left to declare for this slot, so move straight on.
+            let turn_entity = match turn_tracker
+                .get_current_combatant()
+                .filter(|combatant| ecs.entry_ref(combatant.entity).is_ok())
+            {
+                Some(combatant) => combatant.entity,
+                None => {
                     turn_tracker.next_turn();
+                    return;
                 }
+            };
+
+            let mut msg_query = <(&Message, &Source, &ActionDeclarationFinished)>::query();
+
+            if msg_query
+                .iter(ecs)
+                .any(|(_, src, _)| src.entity == turn_entity)
+            {
+                turn_tracker.next_turn();
             }
         }
         _ => {
-            *timer += get_frame_time();
+            (0..timer.tick(get_frame_time())).for_each(|_| turn_tracker.next_turn());
+        }
+    }
+}
 
-            while *timer >= 0.5 {
-                *timer -= 0.5;
-                turn_tracker.next_turn();
-            }
+#[system]
+fn update_animations(
+    #[resource] animations: &mut Animations,
+    #[resource] events: &CombatEvents,
+    #[resource] timer: &PhaseTimer,
+) {
+    events
+        .events()
+        .iter()
+        .for_each(|event| animations.handle_event(event));
+
+    match timer.speed {
+        PhaseSpeed::Instant => animations.skip(),
+        _ => {
+            let speed_up = timer.speed.step_duration() / timer.step_duration().max(f32::EPSILON);
+            animations.update(get_frame_time() * speed_up);
         }
     }
 }
 
+#[system]
+fn update_audio(
+    #[resource] audio: &mut AudioManager,
+    #[resource] events: &CombatEvents,
+    #[resource] turn_tracker: &TurnTracker,
+    #[resource] bindings: &InputBindings,
+) {
+    events
+        .events()
+        .iter()
+        .filter_map(get_combat_event_sound)
+        .chain(
+            turn_tracker
+                .events()
+                .iter()
+                .filter_map(get_turn_event_sound),
+        )
+        .for_each(|effect| audio.play(effect));
+
+    let mut settings = audio.settings;
+    if bindings.is_pressed(InputAction::VolumeUp) {
+        settings.change_master(1);
+    }
+    if bindings.is_pressed(InputAction::VolumeDown) {
+        settings.change_master(-1);
+    }
+    if bindings.is_pressed(InputAction::Mute) {
+        settings.muted = !settings.muted;
+    }
+
+    if settings != audio.settings {
+        audio.update_settings(settings);
+    }
+}
+
+#[system]
+fn phase_timer_controls(#[resource] timer: &mut PhaseTimer, #[resource] bindings: &InputBindings) {
+    if bindings.is_pressed(InputAction::Pause) {
+        timer.toggle_pause();
+    }
+
+    if bindings.is_pressed(InputAction::Step) {
+        timer.request_step();
+    }
+
+    timer.fast_forward = bindings.is_down(InputAction::FastForward);
+
+    [
+        (InputAction::SpeedInstant, PhaseSpeed::Instant),
+        (InputAction::SpeedNormal, PhaseSpeed::Normal),
+        (InputAction::SpeedSlow, PhaseSpeed::Slow),
+    ]
+    .iter()
+    .filter(|(action, _)| bindings.is_pressed(*action))
+    .for_each(|(_, speed)| {
+        timer.speed = *speed;
+        timer.reset();
+    });
+}
+
 #[system]
 #[read_component(Enemy)]
 #[read_component(Coordinate)]
+#[read_component(Movement)]
+#[read_component(Abilities)]
 fn declare_ai_action(
     ecs: &mut SubWorld,
     commands: &mut CommandBuffer,
     #[resource] turn_tracker: &TurnTracker,
     #[resource] grid: &BattleGrid,
-    #[resource] timer: &mut f32,
+    #[resource] timer: &mut PhaseTimer,
+    #[resource] rng: &mut StdRng,
+    #[resource] difficulty: &Difficulty,
 ) {
-    if let Ok(current_combatant) = ecs.entry_ref(turn_tracker.get_current_combatant().entity) {
-        *timer += get_frame_time();
+    let current_entity = match turn_tracker.get_current_combatant() {
+        Some(combatant) => combatant.entity,
+        None => return,
+    };
 
-        if *timer >= 0.5 {
-            *timer = 0.0;
+    if let Ok(current_combatant) = ecs.entry_ref(current_entity) {
+        if current_combatant.get_component::<Enemy>().is_err() {
+            return;
+        }
 
+        if timer.tick(get_frame_time()) > 0 {
             if let Ok(coord) = current_combatant.get_component::<Coordinate>() {
-                let rand_walk = generate_random_walk(*coord, grid, 1, 3, None);
+                let (max_steps, diagonal) = current_combatant
+                    .get_component::<Movement>()
+                    .map(|movement| (movement.max, movement.diagonal))
+                    .unwrap_or((3, false));
+                let rand_walk =
+                    generate_random_walk(*coord, grid, 1, max_steps, None, diagonal, rng);
+                let destination = get_walk_destination(*coord, &rand_walk);
+
+                let ability = current_combatant
+                    .get_component::<Abilities>()
+                    .ok()
+                    .and_then(|abilities| {
+                        choose_ability(&abilities.list, difficulty.aggression, rng)
+                    });
+                let aim = ability.and_then(|ability| {
+                    choose_attack_target(&ability.effects, destination, grid, rng)
+                });
+
+                if let (Some(ability), Some(aim)) = (ability, aim) {
+                    let outcome = interpret_effects(&ability.effects, destination, aim, grid);
+
+                    if !outcome.card_changes.is_empty() {
+                        commands.push((
+                            (),
+                            Message,
+                            Source {
+                                entity: current_entity,
+                            },
+                            CardChanges {
+                                list: outcome.card_changes.clone(),
+                            },
+                            Round,
+                        ));
+                    }
+
+                    if outcome.forced_move != 0 {
+                        commands.push((
+                            (),
+                            Message,
+                            Source {
+                                entity: current_entity,
+                            },
+                            ForcedMove {
+                                targets: outcome.targets.clone(),
+                                origin: destination,
+                                distance: outcome.forced_move,
+                            },
+                            Round,
+                        ));
+                    }
+
+                    if !outcome.initiative_changes.is_empty() {
+                        commands.push((
+                            (),
+                            Message,
+                            Source {
+                                entity: current_entity,
+                            },
+                            InitiativeChange {
+                                targets: outcome.targets.clone(),
+                                changes: outcome.initiative_changes.clone(),
+                            },
+                            Round,
+                        ));
+                    }
+
+                    if !outcome.hazards.is_empty() {
+                        commands.push((
+                            (),
+                            Message,
+                            Source {
+                                entity: current_entity,
+                            },
+                            PlaceHazards {
+                                targets: outcome.targets.clone(),
+                                hazards: outcome.hazards.clone(),
+                            },
+                            Round,
+                        ));
+                    }
+
+                    if outcome.damage > 0 {
+                        commands.push((
+                            (),
+                            Message,
+                            Source {
+                                entity: current_entity,
+                            },
+                            Attack {
+                                targets: outcome.targets,
+                                damage: outcome.damage,
+                            },
+                            Round,
+                        ));
+                    }
+                }
 
                 commands.push((
                     (),
                     Message,
                     Source {
-                        entity: turn_tracker.get_current_combatant().entity,
+                        entity: current_entity,
                     },
                     Move { dirs: rand_walk },
                     Round,
@@ -142,7 +507,7 @@ fn declare_ai_action(
                 Message,
                 ActionDeclarationFinished,
                 Source {
-                    entity: turn_tracker.get_current_combatant().entity,
+                    entity: current_entity,
                 },
                 Round,
             ));
@@ -157,7 +522,9 @@ fn clear_round_messages(commands: &mut CommandBuffer, entity: &Entity, _msg: &Me
 
 #[system(for_each)]
 #[write_component(Coordinate)]
+#[write_component(Health)]
 #[read_component(TileStatus)]
+#[allow(clippy::too_many_arguments)]
 fn resolve_moves(
     ecs: &mut SubWorld,
     commands: &mut CommandBuffer,
@@ -166,43 +533,31 @@ fn resolve_moves(
     src: &Source,
     mv: &Move,
     #[resource] grid: &mut BattleGrid,
-    #[resource] turn_tracker: &TurnTracker,
+    #[resource] turn_tracker: &mut TurnTracker,
+    #[resource] events: &mut CombatEvents,
 ) {
     let mut coord_query = <(Entity, &mut Coordinate, &TileStatus)>::query();
+    let mut entered = Vec::new();
 
     if let Some(start) = coord_query
         .iter_mut(ecs)
-        .filter(|(entity, _, _)| {
-            **entity == src.entity && turn_tracker.get_current_combatant().entity == src.entity
-        })
-        .nth(0)
+        .find(|(entity, _, _)| **entity == src.entity && turn_tracker.is_current_combatant(entity))
     {
-        let mut final_location = mv
-            .dirs
-            .iter()
-            .fold(*start.1, |accum, item| accum + Coordinate::from(*item));
+        let final_location = get_truncated_destination(*start.1, &mv.dirs, grid);
 
-        eprintln!(
-            "Moving from space: {:?} to {final_location:?} with directions {:?}",
-            start.1, mv.dirs
-        );
+        if let Some(final_location) = final_location {
+            let steps = get_steps_before_ice(*start.1, &mv.dirs, grid);
+            let mut path = get_walk_path(*start.1, &mv.dirs[..steps]);
+            // The walk is truncated from the end, so the last visit to the final cell is the stop.
+            if let Some(stop) = path.iter().rposition(|coord| *coord == final_location) {
+                path.truncate(stop + 1);
+            }
+            entered = path.clone();
+            events.push(CombatEvent::Moved {
+                entity: src.entity,
+                path,
+            });
 
-        if mv
-            .dirs
-            .iter()
-            .rev()
-            .find(|dir| {
-                if grid.is_cell_in_bounds(final_location)
-                    && grid.get_status_at_coord(&final_location) == TileStatus::Empty
-                {
-                    return true;
-                } else {
-                    final_location -= Coordinate::from(**dir);
-                    return false;
-                }
-            })
-            .is_some()
-        {
             grid.set_status_at_coord(start.1, TileStatus::Empty);
             *start.1 = final_location;
             grid.set_status_at_coord(&final_location, *start.2);
@@ -210,6 +565,327 @@ fn resolve_moves(
 
         commands.remove(*m_entity);
     }
+
+    let damage = get_enter_damage(&entered, grid);
+    if damage != 0 {
+        apply_hazard(
+            ecs,
+            src.entity,
+            damage,
+            commands,
+            grid,
+            turn_tracker,
+            events,
+        );
+    }
+}
+
+#[system(for_each)]
+#[read_component(Coordinate)]
+#[read_component(Player)]
+#[write_component(Health)]
+#[allow(clippy::too_many_arguments)]
+fn resolve_attacks(
+    ecs: &mut SubWorld,
+    commands: &mut CommandBuffer,
+    m_entity: &Entity,
+    _msg: &Message,
+    src: &Source,
+    attack: &Attack,
+    #[resource] grid: &mut BattleGrid,
+    #[resource] turn_tracker: &mut TurnTracker,
+    #[resource] events: &mut CombatEvents,
+) {
+    if !turn_tracker.is_current_combatant(&src.entity) {
+        return;
+    }
+
+    let mut target_query = <(Entity, &Coordinate, &mut Health)>::query();
+    target_query
+        .iter_mut(ecs)
+        .filter(|(_, coord, _)| attack.targets.contains(coord))
+        .for_each(|(entity, coord, health)| {
+            deal_damage(
+                (*entity, *coord, health),
+                attack.damage,
+                commands,
+                grid,
+                turn_tracker,
+                events,
+            );
+        });
+
+    // The dragon has no cell of its own, so it's hit through its origin.
+    let dragon = get_dragon_origin(grid);
+    if attack.targets.contains(&dragon) {
+        <(Entity, &Player, &mut Health)>::query()
+            .iter_mut(ecs)
+            .filter(|(_, _, health)| health.current > 0)
+            .for_each(|(entity, _, health)| {
+                deal_damage(
+                    (*entity, dragon, health),
+                    attack.damage,
+                    commands,
+                    grid,
+                    turn_tracker,
+                    events,
+                );
+            });
+    }
+
+    commands.remove(*m_entity);
+}
+
+// Runs after `resolve_attacks`, so pieces knocked out by the hit aren't shoved as well.
+#[system(for_each)]
+#[write_component(Coordinate)]
+#[write_component(Health)]
+#[read_component(TileStatus)]
+#[allow(clippy::too_many_arguments)]
+fn resolve_forced_moves(
+    ecs: &mut SubWorld,
+    commands: &mut CommandBuffer,
+    m_entity: &Entity,
+    _msg: &Message,
+    src: &Source,
+    forced: &ForcedMove,
+    #[resource] grid: &mut BattleGrid,
+    #[resource] turn_tracker: &mut TurnTracker,
+    #[resource] events: &mut CombatEvents,
+) {
+    if !turn_tracker.is_current_combatant(&src.entity) {
+        return;
+    }
+
+    let mut pieces = <(Entity, &Coordinate, &Health)>::query()
+        .iter(ecs)
+        .filter(|(_, coord, health)| forced.targets.contains(coord) && health.current > 0)
+        .map(|(entity, coord, _)| (*entity, *coord))
+        .collect::<Vec<(Entity, Coordinate)>>();
+
+    // Move the piece furthest along first, so the ones behind it have room to follow.
+    pieces.sort_by_key(|(_, coord)| {
+        coord.chebyshev_distance(forced.origin) * -forced.distance.signum()
+    });
+
+    pieces.iter().for_each(|(entity, start)| {
+        let (path, collision) = get_forced_path(*start, forced.origin, forced.distance, grid);
+        let end = *path.last().unwrap();
+
+        if end != *start {
+            if let Ok(mut entry) = ecs.entry_mut(*entity) {
+                let status = entry
+                    .get_component::<TileStatus>()
+                    .copied()
+                    .unwrap_or(TileStatus::Occupied);
+                if let Ok(coord) = entry.get_component_mut::<Coordinate>() {
+                    *coord = end;
+                }
+
+                grid.set_status_at_coord(start, TileStatus::Empty);
+                grid.set_status_at_coord(&end, status);
+                events.push(CombatEvent::Moved {
+                    entity: *entity,
+                    path: path.clone(),
+                });
+            }
+
+            let damage = get_enter_damage(&path, grid);
+            if damage != 0 {
+                apply_hazard(ecs, *entity, damage, commands, grid, turn_tracker, events);
+            }
+        }
+
+        if let Some(hit) = collision {
+            let struck = <(Entity, &Coordinate, &Health)>::query()
+                .iter(ecs)
+                .filter(|(other, coord, health)| {
+                    (*other == entity || **coord == hit) && health.current > 0
+                })
+                .map(|(other, _, _)| *other)
+                .collect::<Vec<Entity>>();
+
+            let mut struck_query = <(Entity, &Coordinate, &mut Health)>::query();
+            struck_query
+                .iter_mut(ecs)
+                .filter(|(other, _, _)| struck.contains(other))
+                .for_each(|(other, coord, health)| {
+                    deal_damage(
+                        (*other, *coord, health),
+                        COLLISION_DAMAGE,
+                        commands,
+                        grid,
+                        turn_tracker,
+                        events,
+                    );
+                });
+        }
+    });
+
+    commands.remove(*m_entity);
+}
+
+#[system(for_each)]
+fn resolve_hazard_placement(
+    commands: &mut CommandBuffer,
+    m_entity: &Entity,
+    _msg: &Message,
+    src: &Source,
+    placement: &PlaceHazards,
+    #[resource] grid: &mut BattleGrid,
+    #[resource] turn_tracker: &TurnTracker,
+) {
+    if !turn_tracker.is_current_combatant(&src.entity) {
+        return;
+    }
+
+    placement.targets.iter().for_each(|coord| {
+        placement
+            .hazards
+            .iter()
+            .for_each(|(kind, rounds)| grid.place_hazard(*coord, *kind, *rounds))
+    });
+
+    commands.remove(*m_entity);
+}
+
+// Hazards trigger on whoever ends their turn standing in them, and wear off as rounds end.
+#[system]
+#[read_component(Coordinate)]
+#[write_component(Health)]
+fn trigger_hazards(
+    ecs: &mut SubWorld,
+    commands: &mut CommandBuffer,
+    #[resource] grid: &mut BattleGrid,
+    #[resource] turn_tracker: &mut TurnTracker,
+    #[resource] events: &mut CombatEvents,
+) {
+    let turn_events = turn_tracker.events().to_vec();
+
+    turn_events.iter().for_each(|event| match event {
+        TurnEvent::TurnEnded(entity) => {
+            let hazard = ecs
+                .entry_ref(*entity)
+                .ok()
+                .and_then(|entry| entry.get_component::<Coordinate>().ok().copied())
+                .and_then(|coord| grid.get_hazard_at(coord).copied());
+
+            if let Some(hazard) = hazard {
+                let amount = hazard.kind.end_of_turn_damage();
+                if amount != 0 {
+                    apply_hazard(ecs, *entity, amount, commands, grid, turn_tracker, events);
+                }
+            }
+        }
+
+        TurnEvent::RoundEnded(_) => grid.tick_hazards(),
+        _ => {}
+    });
+}
+
+// Hurts the entity, or heals it for a negative amount, without going over its max health.
+fn apply_hazard(
+    ecs: &mut SubWorld,
+    entity: Entity,
+    amount: i32,
+    commands: &mut CommandBuffer,
+    grid: &mut BattleGrid,
+    turn_tracker: &mut TurnTracker,
+    events: &mut CombatEvents,
+) {
+    let mut query = <(Entity, &Coordinate, &mut Health)>::query();
+    if let Some((_, coord, health)) = query
+        .iter_mut(ecs)
+        .find(|(other, _, health)| **other == entity && health.current > 0)
+    {
+        if amount > 0 {
+            deal_damage(
+                (entity, *coord, health),
+                amount,
+                commands,
+                grid,
+                turn_tracker,
+                events,
+            );
+        } else {
+            let healed = (-amount).min(health.max - health.current);
+            if healed > 0 {
+                health.current += healed;
+                events.push(CombatEvent::Healed {
+                    entity,
+                    coord: *coord,
+                    amount: healed,
+                });
+            }
+        }
+    }
+}
+
+fn deal_damage(
+    (entity, coord, health): (Entity, Coordinate, &mut Health),
+    amount: i32,
+    commands: &mut CommandBuffer,
+    grid: &mut BattleGrid,
+    turn_tracker: &mut TurnTracker,
+    events: &mut CombatEvents,
+) {
+    health.current -= amount;
+    events.push(CombatEvent::Damaged {
+        entity,
+        coord,
+        amount,
+    });
+
+    if health.current <= 0 {
+        events.push(CombatEvent::Died { entity, coord });
+        // The dragon sits off the grid, so it leaves no cell behind.
+        if grid.is_cell_in_bounds(coord) {
+            grid.set_status_at_coord(&coord, TileStatus::Empty);
+        }
+        turn_tracker.remove_combatant(&entity);
+        commands.remove(entity);
+    }
+}
+
+#[system(for_each)]
+#[read_component(Coordinate)]
+#[read_component(Player)]
+#[allow(clippy::too_many_arguments)]
+fn resolve_initiative_changes(
+    ecs: &SubWorld,
+    commands: &mut CommandBuffer,
+    m_entity: &Entity,
+    _msg: &Message,
+    src: &Source,
+    change: &InitiativeChange,
+    #[resource] grid: &BattleGrid,
+    #[resource] turn_tracker: &mut TurnTracker,
+) {
+    if !turn_tracker.is_current_combatant(&src.entity) {
+        return;
+    }
+
+    let mut hit = <(Entity, &Coordinate)>::query()
+        .iter(ecs)
+        .filter(|(_, coord)| change.targets.contains(coord))
+        .map(|(entity, _)| *entity)
+        .collect::<Vec<Entity>>();
+    if change.targets.contains(&get_dragon_origin(grid)) {
+        hit.extend(
+            <(Entity, &Player)>::query()
+                .iter(ecs)
+                .map(|(entity, _)| *entity),
+        );
+    }
+
+    hit.iter().for_each(|entity| {
+        change
+            .changes
+            .iter()
+            .for_each(|(amount, timing)| turn_tracker.adjust_initiative(entity, *amount, *timing))
+    });
+
+    commands.remove(*m_entity);
 }
 
 #[system(for_each)]
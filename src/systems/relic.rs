@@ -0,0 +1,162 @@
+use crate::prelude::*;
+
+// Fires relics off this frame's turn and combat events. Relic effects are cast from the dragon at a
+// random hero the effect can reach, or at the fallen hero for death triggers. They can deal damage,
+// leave hazards and change cards; shoves, block and initiative changes aren't applied.
+#[system]
+#[read_component(Enemy)]
+#[read_component(Coordinate)]
+#[write_component(Health)]
+#[read_component(Message)]
+#[read_component(PlayCard)]
+#[allow(clippy::too_many_arguments)]
+pub fn trigger_relics(
+    ecs: &mut SubWorld,
+    commands: &mut CommandBuffer,
+    #[resource] relics: &Relics,
+    #[resource] grid: &mut BattleGrid,
+    #[resource] turn_tracker: &mut TurnTracker,
+    #[resource] events: &mut CombatEvents,
+    #[resource] card_zones: &mut CardZones,
+    #[resource] db: &mut CardDB,
+    #[resource] rng: &mut StdRng,
+) {
+    let mut triggers = Vec::new();
+    turn_tracker.events().iter().for_each(|event| {
+        if let TurnEvent::RoundStarted(round) = event {
+            if *round == 1 {
+                triggers.push((RelicTrigger::StartOfCombat, None));
+            }
+            triggers.push((RelicTrigger::StartOfRound, None));
+        }
+    });
+    // The dragon dies off the grid, at its origin, and that's no hero's death.
+    let dragon = get_dragon_origin(grid);
+    events.events().iter().for_each(|event| match event {
+        CombatEvent::CardResolved { .. } => triggers.push((RelicTrigger::CardPlayed, None)),
+        CombatEvent::Died { coord, .. } if *coord != dragon => {
+            triggers.push((RelicTrigger::HeroDeath, Some(*coord)))
+        }
+        _ => {}
+    });
+
+    triggers.iter().for_each(|(trigger, aim)| {
+        relics
+            .list
+            .iter()
+            .filter(|relic| relic.trigger == *trigger)
+            .for_each(|relic| {
+                let heroes = <(&Enemy, &Coordinate)>::query()
+                    .iter(ecs)
+                    .map(|(_, coord)| *coord)
+                    .collect::<Vec<Coordinate>>();
+                let origin = get_dragon_origin(grid);
+                // Heroes behind cover are out of reach of some shapes, so only aim at ones the
+                // effect would actually land on.
+                let reachable = heroes
+                    .iter()
+                    .filter(|hero| {
+                        interpret_effects(&relic.effects, origin, **hero, grid)
+                            .targets
+                            .contains(hero)
+                    })
+                    .copied()
+                    .collect::<Vec<Coordinate>>();
+                let aim = aim
+                    .or_else(|| {
+                        reachable
+                            .choose(rng)
+                            .or_else(|| heroes.choose(rng))
+                            .copied()
+                    })
+                    .unwrap_or(origin);
+                let outcome = interpret_effects(&relic.effects, origin, aim, grid);
+
+                if outcome.damage > 0 {
+                    let mut target_query = <(Entity, &Coordinate, &mut Health)>::query();
+                    target_query
+                        .iter_mut(ecs)
+                        .filter(|(_, coord, health)| {
+                            outcome.targets.contains(coord) && health.current > 0
+                        })
+                        .for_each(|(entity, coord, health)| {
+                            super::deal_damage(
+                                (*entity, *coord, health),
+                                outcome.damage,
+                                commands,
+                                grid,
+                                turn_tracker,
+                                events,
+                            );
+                        });
+                }
+
+                outcome.targets.iter().for_each(|coord| {
+                    outcome
+                        .hazards
+                        .iter()
+                        .for_each(|(kind, rounds)| grid.place_hazard(*coord, *kind, *rounds))
+                });
+
+                let queued = <(&Message, &PlayCard)>::query()
+                    .iter(ecs)
+                    .map(|(_, play)| play.card)
+                    .collect::<Vec<Entity>>();
+                super::card::apply_card_changes(
+                    &outcome.card_changes,
+                    turn_tracker.turn_state,
+                    &queued,
+                    commands,
+                    card_zones,
+                    db,
+                    rng,
+                );
+            });
+    });
+}
+
+#[system]
+pub fn draw_relic_bar(
+    #[resource] relics: &Relics,
+    #[resource] layout: &Layout,
+    #[resource] textures: &mut TextureAssets,
+) {
+    let mouse = Vec2::from(mouse_position());
+
+    layout_relic_bar(layout, relics.list.len())
+        .iter()
+        .zip(relics.list.iter())
+        .for_each(|(rect, relic)| {
+            match textures.get_sprite(relic.icon.as_ref()) {
+                Some(texture) => draw_texture_in_rect(texture, *rect),
+                None => {
+                    draw_rectangle(rect.x, rect.y, rect.w, rect.h, GOLD);
+                    draw_text(
+                        &relic.name.chars().next().unwrap_or('?').to_string(),
+                        rect.x + rect.w * 0.3,
+                        rect.y + rect.h * 0.75,
+                        rect.h * 0.7,
+                        BLACK,
+                    );
+                }
+            }
+
+            if rect.contains(mouse) {
+                let font_size = 24.0 * layout.scale;
+                draw_text(
+                    &relic.name,
+                    rect.x,
+                    rect.bottom() + font_size,
+                    font_size,
+                    GOLD,
+                );
+                draw_text(
+                    &relic.description,
+                    rect.x,
+                    rect.bottom() + font_size * 2.0,
+                    font_size,
+                    WHITE,
+                );
+            }
+        });
+}
@@ -0,0 +1,274 @@
+use crate::prelude::*;
+
+// The shapes cards and abilities can hit. Everything is measured from the cell being aimed at,
+// or from the caster for shapes that travel outwards.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetShape {
+    Single,
+    // Lines through the aimed cell, reaching this far to either side.
+    Vertical(i32),
+    Horizontal(i32),
+    // Every cell within this many steps of the aimed cell.
+    Radius(i32),
+    // Widens by a cell on each side per step away from the caster, towards the aimed cell.
+    Cone(i32),
+    // Straight out from the caster towards the aimed cell.
+    Line(i32),
+    // Whole rows, counted from the top.
+    Rows { first: i32, last: i32 },
+    // The rows closest to the dragon.
+    BottomRows(i32),
+    // Jumps from the aimed cell to the nearest occupied cell within `range`, `bounces` times.
+    Chain { bounces: i32, range: i32 },
+}
+
+impl TargetShape {
+    // Shapes that travel out from the caster stop at cover. The rest land where they're aimed.
+    pub fn is_blocked_by_cover(&self) -> bool {
+        match self {
+            TargetShape::Single
+            | TargetShape::Vertical(_)
+            | TargetShape::Horizontal(_)
+            | TargetShape::Cone(_)
+            | TargetShape::Line(_) => true,
+
+            TargetShape::Radius(_)
+            | TargetShape::Rows { .. }
+            | TargetShape::BottomRows(_)
+            | TargetShape::Chain { .. } => false,
+        }
+    }
+
+    // Shapes that set off from the caster, so they can be fired at something out of reach.
+    pub fn travels_from_caster(&self) -> bool {
+        matches!(self, TargetShape::Cone(_) | TargetShape::Line(_))
+    }
+}
+
+// The dragon sits just past the bottom edge of the grid, in the middle column.
+pub fn get_dragon_origin(grid: &BattleGrid) -> Coordinate {
+    Coordinate {
+        x: grid.width / 2,
+        y: grid.height,
+    }
+}
+
+// The straight direction that best points from one cell to another. Vertical wins ties, since
+// that's the way the dragon faces.
+pub fn get_direction_towards(from: Coordinate, to: Coordinate) -> Option<Direction> {
+    let offset = to - from;
+
+    match (offset.x, offset.y) {
+        (0, 0) => None,
+        (x, y) if y.abs() >= x.abs() && y < 0 => Some(Direction::Up),
+        (x, y) if y.abs() >= x.abs() => Some(Direction::Down),
+        (x, _) if x < 0 => Some(Direction::Left),
+        _ => Some(Direction::Right),
+    }
+}
+
+// Every in-bounds cell the shape covers, without duplicates, nearest the origin first. The
+// dragon's origin just past the bottom edge counts too, so heroes can hit it, but the dragon
+// never hits itself.
+pub fn get_affected_cells(
+    shape: &TargetShape,
+    caster: Coordinate,
+    aim: Coordinate,
+    grid: &BattleGrid,
+) -> Vec<Coordinate> {
+    let cells = match shape {
+        TargetShape::Single => vec![aim],
+
+        TargetShape::Vertical(reach) => (-reach..=*reach)
+            .map(|offset| aim + Coordinate::from(Direction::Down) * offset)
+            .collect(),
+
+        TargetShape::Horizontal(reach) => (-reach..=*reach)
+            .map(|offset| aim + Coordinate::from(Direction::Right) * offset)
+            .collect(),
+
+        TargetShape::Radius(radius) => (-radius..=*radius)
+            .flat_map(|y| (-radius..=*radius).map(move |x| aim + Coordinate { x, y }))
+            .filter(|coord| coord.manhattan_distance(aim) <= *radius)
+            .collect(),
+
+        TargetShape::Cone(length) => match get_direction_towards(caster, aim) {
+            // Laid out facing up, then turned to face the aim.
+            Some(dir) => (1..=*length)
+                .flat_map(|step| {
+                    (-(step - 1)..step).map(move |width| Coordinate { x: width, y: -step })
+                })
+                .map(|offset| caster + offset.rotate_to_face(dir))
+                .collect(),
+            None => vec![aim],
+        },
+
+        TargetShape::Line(length) => match get_direction_towards(caster, aim) {
+            Some(dir) => (1..=*length)
+                .map(|step| caster + Coordinate::from(dir) * step)
+                .collect(),
+            None => vec![aim],
+        },
+
+        TargetShape::Rows { first, last } => (*first..=*last)
+            .flat_map(|y| (0..grid.width).map(move |x| Coordinate { x, y }))
+            .collect(),
+
+        TargetShape::BottomRows(count) => (grid.height - count..grid.height)
+            .flat_map(|y| (0..grid.width).map(move |x| Coordinate { x, y }))
+            .collect(),
+
+        TargetShape::Chain { bounces, range } => get_chain_cells(aim, *bounces, *range, grid),
+    };
+
+    let dragon = get_dragon_origin(grid);
+    let mut affected: Vec<Coordinate> = Vec::new();
+    cells
+        .into_iter()
+        .filter(|coord| grid.is_cell_in_bounds(*coord) || (*coord == dragon && caster != dragon))
+        .for_each(|coord| {
+            if !affected.contains(&coord) {
+                affected.push(coord);
+            }
+        });
+
+    affected
+}
+
+fn get_chain_cells(
+    aim: Coordinate,
+    bounces: i32,
+    range: i32,
+    grid: &BattleGrid,
+) -> Vec<Coordinate> {
+    let occupied = (0..grid.height)
+        .flat_map(|y| (0..grid.width).map(move |x| Coordinate { x, y }))
+        .filter(|coord| grid.get_status_at_coord(coord).is_occupied())
+        .collect::<Vec<Coordinate>>();
+
+    let mut hit = vec![aim];
+    for _ in 0..bounces {
+        let current = *hit.last().unwrap();
+        let next = occupied
+            .iter()
+            .filter(|coord| !hit.contains(coord))
+            .filter(|coord| coord.manhattan_distance(current) <= range)
+            .min_by_key(|coord| coord.manhattan_distance(current));
+
+        match next {
+            Some(next) => hit.push(*next),
+            None => break,
+        }
+    }
+
+    hit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coords(cells: &[(i32, i32)]) -> Vec<Coordinate> {
+        cells
+            .iter()
+            .map(|(x, y)| Coordinate { x: *x, y: *y })
+            .collect()
+    }
+
+    #[test]
+    fn test_radius_is_a_diamond() {
+        let grid = BattleGrid::new();
+        let cells = get_affected_cells(
+            &TargetShape::Radius(1),
+            Coordinate { x: 0, y: 0 },
+            Coordinate { x: 2, y: 2 },
+            &grid,
+        );
+
+        assert_eq!(cells, coords(&[(2, 1), (1, 2), (2, 2), (3, 2), (2, 3)]));
+    }
+
+    #[test]
+    fn test_cone_widens_from_the_dragon() {
+        let grid = BattleGrid::new();
+        let dragon = get_dragon_origin(&grid);
+        let cells = get_affected_cells(
+            &TargetShape::Cone(2),
+            dragon,
+            Coordinate { x: 2, y: 0 },
+            &grid,
+        );
+
+        assert_eq!(dragon, Coordinate { x: 2, y: 5 });
+        assert_eq!(cells, coords(&[(2, 4), (1, 3), (2, 3), (3, 3)]));
+    }
+
+    #[test]
+    fn test_line_follows_the_aim() {
+        let grid = BattleGrid::new();
+        let cells = get_affected_cells(
+            &TargetShape::Line(3),
+            Coordinate { x: 1, y: 1 },
+            Coordinate { x: 4, y: 2 },
+            &grid,
+        );
+
+        assert_eq!(cells, coords(&[(2, 1), (3, 1), (4, 1)]));
+    }
+
+    #[test]
+    fn test_heroes_can_reach_the_dragon() {
+        let grid = BattleGrid::new();
+        let dragon = get_dragon_origin(&grid);
+
+        let shot = get_affected_cells(
+            &TargetShape::Line(3),
+            Coordinate { x: 2, y: 3 },
+            dragon,
+            &grid,
+        );
+        assert_eq!(shot, coords(&[(2, 4), (2, 5)]));
+
+        let breath = get_affected_cells(
+            &TargetShape::Radius(1),
+            dragon,
+            Coordinate { x: 2, y: 4 },
+            &grid,
+        );
+        assert!(!breath.contains(&dragon));
+    }
+
+    #[test]
+    fn test_bottom_rows() {
+        let grid = BattleGrid::new();
+        let cells = get_affected_cells(
+            &TargetShape::BottomRows(1),
+            get_dragon_origin(&grid),
+            Coordinate { x: 0, y: 0 },
+            &grid,
+        );
+
+        assert_eq!(cells, coords(&[(0, 4), (1, 4), (2, 4), (3, 4), (4, 4)]));
+    }
+
+    #[test]
+    fn test_chain_bounces_to_nearest_occupied() {
+        let mut grid = BattleGrid::new();
+        [(0, 0), (0, 2), (4, 4)].iter().for_each(|(x, y)| {
+            grid.set_status_at_coord(&Coordinate { x: *x, y: *y }, TileStatus::Occupied)
+        });
+
+        let cells = get_affected_cells(
+            &TargetShape::Chain {
+                bounces: 3,
+                range: 2,
+            },
+            get_dragon_origin(&grid),
+            Coordinate { x: 0, y: 0 },
+            &grid,
+        );
+
+        // (4, 4) is too far from (0, 2) to bounce to.
+        assert_eq!(cells, coords(&[(0, 0), (0, 2)]));
+    }
+}
@@ -1,24 +1,37 @@
 use crate::prelude::*;
 use std::cmp::Ordering;
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Copy, Clone, Debug)]
 pub struct Combatant {
     pub init: i32,
     pub priority: i32,
+    pub init_mod: i32,
     pub entity: Entity,
+    // Order the combatant joined the fight in, used as the final tie-breaker.
+    pub joined: usize,
+    // Initiative change queued up by a delay or haste to apply on the next roll.
+    pub next_round_adjustment: i32,
 }
 
 impl Combatant {
+    // Higher initiative goes first. Ties are broken by priority, then initiative modifier, then
+    // by whoever joined the fight first.
     fn compare_init(&self, other: &Combatant) -> Ordering {
-        let init_comparison = self.init.cmp(&other.init).reverse();
-        match init_comparison {
-            Ordering::Equal => self.priority.cmp(&other.priority).reverse(),
-
-            _ => init_comparison,
-        }
+        self.init
+            .cmp(&other.init)
+            .then(self.priority.cmp(&other.priority))
+            .then(self.init_mod.cmp(&other.init_mod))
+            .reverse()
+            .then(self.joined.cmp(&other.joined))
     }
 }
 
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum InitiativeTiming {
+    CurrentRound,
+    NextRound,
+}
+
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub enum TurnState {
     StartOfRound,
@@ -26,11 +39,29 @@ pub enum TurnState {
     ResolvePhase,
 }
 
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum TurnEvent {
+    RoundStarted(u32),
+    RoundEnded(u32),
+    PhaseChanged(TurnState),
+    TurnStarted(Entity),
+    // Only raised in the resolve phase, once the combatant's actions have played out.
+    TurnEnded(Entity),
+    CombatantJoined(Entity),
+    CombatantLeft(Entity),
+}
+
 #[derive(PartialEq, Clone)]
 pub struct TurnTracker {
     pub turn_state: TurnState,
     pub current_combatant: usize,
     pub combatants: Vec<Combatant>,
+    pub round: u32,
+    joined_count: usize,
+    current_removed: bool,
+    // Events raised this frame, and the ones raised last frame that systems can read now.
+    pending_events: Vec<TurnEvent>,
+    published_events: Vec<TurnEvent>,
 }
 
 impl TurnTracker {
@@ -39,42 +70,112 @@ impl TurnTracker {
             turn_state: TurnState::StartOfRound,
             current_combatant: 0,
             combatants: Vec::new(),
+            round: 0,
+            joined_count: 0,
+            current_removed: false,
+            pending_events: Vec::new(),
+            published_events: Vec::new(),
         }
     }
 
+    // Called once per frame so every system sees the events raised during the previous frame
+    // exactly once, regardless of where it sits in the schedule.
+    pub fn publish_events(&mut self) {
+        self.published_events = std::mem::take(&mut self.pending_events);
+    }
+
+    pub fn events(&self) -> &[TurnEvent] {
+        &self.published_events
+    }
+
+    fn emit(&mut self, event: TurnEvent) {
+        self.pending_events.push(event);
+    }
+
+    fn emit_turn_started(&mut self) {
+        if let Some(combatant) = self.get_current_combatant() {
+            self.emit(TurnEvent::TurnStarted(combatant.entity));
+        }
+    }
+
+    fn change_phase(&mut self, turn_state: TurnState) {
+        self.turn_state = turn_state;
+        self.emit(TurnEvent::PhaseChanged(turn_state));
+    }
+
     fn order_combatants(&mut self) {
         self.combatants.sort_by(|a, b| a.compare_init(b));
     }
 
+    // Re-sorts mid-round while keeping `current_combatant` pointed at whoever's turn it is.
+    fn reorder_keeping_current(&mut self) {
+        let current_entity = self
+            .combatants
+            .get(self.current_combatant)
+            .map(|combatant| combatant.entity);
+
+        self.order_combatants();
+
+        if let Some(entity) = current_entity {
+            if let Some(idx) = self.index_of(&entity) {
+                self.current_combatant = idx;
+            }
+        }
+    }
+
+    fn index_of(&self, entity: &Entity) -> Option<usize> {
+        self.combatants
+            .iter()
+            .position(|combatant| combatant.entity == *entity)
+    }
+
     pub fn next_turn(&mut self) {
         match self.turn_state {
             TurnState::StartOfRound => {
+                self.current_removed = false;
                 self.order_combatants();
                 self.jump_to_last_combatant();
-                self.turn_state = TurnState::DeclarePhase;
+                self.round += 1;
+                self.emit(TurnEvent::RoundStarted(self.round));
+                self.change_phase(TurnState::DeclarePhase);
+                self.emit_turn_started();
             }
 
             TurnState::DeclarePhase => {
+                // Declaring runs from the back of the order, so removing the acting combatant
+                // needs no special handling here.
+                self.current_removed = false;
                 if self.current_combatant > 0 {
                     self.current_combatant -= 1;
                 } else {
-                    self.turn_state = TurnState::ResolvePhase;
                     self.jump_to_first_combatant();
+                    self.change_phase(TurnState::ResolvePhase);
                 }
+                self.emit_turn_started();
             }
 
             TurnState::ResolvePhase => {
-                if self.current_combatant < self.combatants.len() - 1 {
+                // If the acting combatant was removed the next one already slid into its slot.
+                if !std::mem::take(&mut self.current_removed) {
+                    if let Some(combatant) = self.get_current_combatant() {
+                        self.emit(TurnEvent::TurnEnded(combatant.entity));
+                    }
                     self.current_combatant += 1;
+                }
+
+                if self.current_combatant < self.combatants.len() {
+                    self.emit_turn_started();
                 } else {
-                    self.turn_state = TurnState::StartOfRound;
+                    self.jump_to_first_combatant();
+                    self.emit(TurnEvent::RoundEnded(self.round));
+                    self.change_phase(TurnState::StartOfRound);
                 }
             }
         }
     }
 
     fn jump_to_last_combatant(&mut self) {
-        self.current_combatant = self.combatants.len() - 1;
+        self.current_combatant = self.combatants.len().saturating_sub(1);
     }
 
     fn jump_to_first_combatant(&mut self) {
@@ -82,30 +183,309 @@ impl TurnTracker {
     }
 
     // Adds an entity to the combatants list at the specified initiative. Or updates the initiative
-    // of an already existing entity.
-    pub fn register_combatant(&mut self, entity: &Entity, init: i32, priority: i32) {
+    // of an already existing entity, applying any delay or haste queued for this round.
+    pub fn register_combatant(&mut self, entity: &Entity, init: i32, priority: i32, init_mod: i32) {
         if let Some(existing) = self
             .combatants
             .iter_mut()
-            .filter(|combatant| combatant.entity == *entity)
-            .nth(0)
+            .find(|combatant| combatant.entity == *entity)
         {
-            existing.init = init;
+            existing.init = init + std::mem::take(&mut existing.next_round_adjustment);
             existing.priority = priority;
+            existing.init_mod = init_mod;
         } else {
             self.combatants.push(Combatant {
                 entity: *entity,
                 init,
                 priority,
-            })
+                init_mod,
+                joined: self.joined_count,
+                next_round_adjustment: 0,
+            });
+            self.joined_count += 1;
+            self.emit(TurnEvent::CombatantJoined(*entity));
         }
 
         if self.turn_state != TurnState::StartOfRound {
-            self.order_combatants();
+            self.reorder_keeping_current();
+        }
+    }
+
+    // Delays (negative amount) or hastens (positive amount) a combatant. Changes for the current
+    // round take effect immediately, but never make a combatant act twice or skip their turn.
+    pub fn adjust_initiative(&mut self, entity: &Entity, amount: i32, timing: InitiativeTiming) {
+        if let Some(idx) = self.index_of(entity) {
+            match timing {
+                InitiativeTiming::CurrentRound => {
+                    self.combatants[idx].init += amount;
+                    if self.turn_state != TurnState::StartOfRound {
+                        self.reorder_keeping_current();
+                    }
+                }
+
+                InitiativeTiming::NextRound => {
+                    self.combatants[idx].next_round_adjustment += amount;
+                }
+            }
+        }
+    }
+
+    // Removes a combatant (usually because they died) without shifting whose turn it is.
+    pub fn remove_combatant(&mut self, entity: &Entity) {
+        if let Some(idx) = self.index_of(entity) {
+            self.combatants.remove(idx);
+            self.emit(TurnEvent::CombatantLeft(*entity));
+
+            match idx.cmp(&self.current_combatant) {
+                Ordering::Less => self.current_combatant -= 1,
+                Ordering::Equal => self.current_removed = true,
+                Ordering::Greater => {}
+            }
+        }
+    }
+
+    // `None` when nobody is fighting, or the acting combatant was removed mid-turn.
+    pub fn get_current_combatant(&self) -> Option<Combatant> {
+        match self.current_removed {
+            true => None,
+            false => self.combatants.get(self.current_combatant).copied(),
+        }
+    }
+
+    pub fn is_current_combatant(&self, entity: &Entity) -> bool {
+        self.get_current_combatant()
+            .is_some_and(|combatant| combatant.entity == *entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker_with(world: &mut World, inits: &[i32]) -> (TurnTracker, Vec<Entity>) {
+        let mut tracker = TurnTracker::new();
+        let entities = inits
+            .iter()
+            .map(|init| {
+                let entity = world.push(());
+                tracker.register_combatant(&entity, *init, 0, 0);
+                entity
+            })
+            .collect::<Vec<Entity>>();
+
+        (tracker, entities)
+    }
+
+    #[test]
+    fn test_start_of_round_moves_to_declare_from_lowest_init() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[3, 9, 5]);
+
+        tracker.next_turn();
+
+        assert_eq!(tracker.turn_state, TurnState::DeclarePhase);
+        assert_eq!(tracker.get_current_combatant().unwrap().entity, entities[0]);
+    }
+
+    #[test]
+    fn test_full_round_phase_order() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[3, 9, 5]);
+
+        let mut seen = Vec::new();
+        tracker.next_turn();
+        while tracker.turn_state != TurnState::StartOfRound {
+            seen.push((
+                tracker.turn_state,
+                tracker.get_current_combatant().unwrap().entity,
+            ));
+            tracker.next_turn();
         }
+
+        let expected = vec![
+            (TurnState::DeclarePhase, entities[0]),
+            (TurnState::DeclarePhase, entities[2]),
+            (TurnState::DeclarePhase, entities[1]),
+            (TurnState::ResolvePhase, entities[1]),
+            (TurnState::ResolvePhase, entities[2]),
+            (TurnState::ResolvePhase, entities[0]),
+        ];
+        assert_eq!(seen, expected);
     }
 
-    pub fn get_current_combatant(&self) -> Combatant {
-        self.combatants[self.current_combatant]
+    #[test]
+    fn test_ties_break_on_priority_then_mod_then_join_order() {
+        let mut world = World::default();
+        let mut tracker = TurnTracker::new();
+        let first = world.push(());
+        let second = world.push(());
+        let modded = world.push(());
+        let player = world.push(());
+
+        tracker.register_combatant(&first, 5, 0, 0);
+        tracker.register_combatant(&second, 5, 0, 0);
+        tracker.register_combatant(&modded, 5, 0, 2);
+        tracker.register_combatant(&player, 5, 100, 0);
+        tracker.next_turn();
+
+        let order = tracker
+            .combatants
+            .iter()
+            .map(|combatant| combatant.entity)
+            .collect::<Vec<Entity>>();
+        assert_eq!(order, vec![player, modded, first, second]);
+    }
+
+    #[test]
+    fn test_delay_next_round_applies_on_reroll() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[5, 4]);
+
+        tracker.adjust_initiative(&entities[0], -3, InitiativeTiming::NextRound);
+        tracker.register_combatant(&entities[0], 5, 0, 0);
+        tracker.next_turn();
+
+        assert_eq!(tracker.combatants[0].entity, entities[1]);
+        assert_eq!(tracker.combatants[1].init, 2);
+
+        tracker.register_combatant(&entities[0], 5, 0, 0);
+        assert_eq!(tracker.combatants[0].init, 5);
+    }
+
+    #[test]
+    fn test_haste_current_round_keeps_current_turn() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[9, 5, 3]);
+        tracker.next_turn();
+        tracker.next_turn();
+        assert_eq!(tracker.get_current_combatant().unwrap().entity, entities[1]);
+
+        tracker.adjust_initiative(&entities[2], 10, InitiativeTiming::CurrentRound);
+
+        assert_eq!(tracker.get_current_combatant().unwrap().entity, entities[1]);
+        assert_eq!(tracker.combatants[0].entity, entities[2]);
+    }
+
+    #[test]
+    fn test_removing_earlier_combatant_keeps_current_turn() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[9, 5, 3]);
+        tracker.next_turn();
+        (0..4).for_each(|_| tracker.next_turn());
+        assert_eq!(tracker.turn_state, TurnState::ResolvePhase);
+        assert_eq!(tracker.get_current_combatant().unwrap().entity, entities[1]);
+
+        tracker.remove_combatant(&entities[0]);
+
+        assert_eq!(tracker.get_current_combatant().unwrap().entity, entities[1]);
+        tracker.next_turn();
+        assert_eq!(tracker.get_current_combatant().unwrap().entity, entities[2]);
+    }
+
+    #[test]
+    fn test_removing_current_combatant_during_resolve_doesnt_skip_next() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[9, 5, 3]);
+        tracker.next_turn();
+        (0..4).for_each(|_| tracker.next_turn());
+
+        tracker.remove_combatant(&entities[1]);
+        tracker.next_turn();
+
+        assert_eq!(tracker.turn_state, TurnState::ResolvePhase);
+        assert_eq!(tracker.get_current_combatant().unwrap().entity, entities[2]);
+
+        tracker.remove_combatant(&entities[2]);
+        tracker.next_turn();
+        assert_eq!(tracker.turn_state, TurnState::StartOfRound);
+    }
+
+    #[test]
+    fn test_empty_tracker_cycles_phases_without_panicking() {
+        let mut tracker = TurnTracker::new();
+
+        tracker.next_turn();
+        assert_eq!(tracker.turn_state, TurnState::DeclarePhase);
+        assert_eq!(tracker.get_current_combatant(), None);
+
+        tracker.next_turn();
+        assert_eq!(tracker.turn_state, TurnState::ResolvePhase);
+        assert_eq!(tracker.get_current_combatant(), None);
+
+        tracker.next_turn();
+        assert_eq!(tracker.turn_state, TurnState::StartOfRound);
+    }
+
+    #[test]
+    fn test_removing_everyone_mid_declare_ends_round_cleanly() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[9, 5]);
+        tracker.next_turn();
+
+        entities
+            .iter()
+            .for_each(|entity| tracker.remove_combatant(entity));
+        assert_eq!(tracker.get_current_combatant(), None);
+
+        tracker.next_turn();
+        tracker.next_turn();
+        assert_eq!(tracker.turn_state, TurnState::StartOfRound);
+    }
+
+    #[test]
+    fn test_reinforcement_joins_mid_round_without_stealing_turn() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[9, 3]);
+        tracker.next_turn();
+        tracker.next_turn();
+        tracker.next_turn();
+        assert_eq!(tracker.turn_state, TurnState::ResolvePhase);
+        assert!(tracker.is_current_combatant(&entities[0]));
+
+        let summon = world.push(());
+        tracker.register_combatant(&summon, 20, 0, 0);
+
+        assert!(tracker.is_current_combatant(&entities[0]));
+        tracker.next_turn();
+        assert!(tracker.is_current_combatant(&entities[1]));
+    }
+
+    #[test]
+    fn test_events_are_published_once() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[9]);
+        tracker.publish_events();
+        assert_eq!(tracker.events(), &[TurnEvent::CombatantJoined(entities[0])]);
+
+        tracker.next_turn();
+        tracker.publish_events();
+        assert_eq!(
+            tracker.events(),
+            &[
+                TurnEvent::RoundStarted(1),
+                TurnEvent::PhaseChanged(TurnState::DeclarePhase),
+                TurnEvent::TurnStarted(entities[0]),
+            ]
+        );
+
+        tracker.publish_events();
+        assert!(tracker.events().is_empty());
+    }
+
+    #[test]
+    fn test_turn_ended_before_round_ended() {
+        let mut world = World::default();
+        let (mut tracker, entities) = tracker_with(&mut world, &[9]);
+        tracker.next_turn();
+        tracker.next_turn();
+        assert_eq!(tracker.turn_state, TurnState::ResolvePhase);
+
+        tracker.publish_events();
+        tracker.next_turn();
+        tracker.publish_events();
+        assert_eq!(
+            &tracker.events()[..2],
+            &[TurnEvent::TurnEnded(entities[0]), TurnEvent::RoundEnded(1),]
+        );
     }
 }